@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use smol_str::SmolStr;
+
+use crate::entities::{Faction, IntelPortal, ModKind};
+
+// owner of Machina deployments
+const MACHINA_OWNER: &str = "__MACHINA__";
+
+// neutral portals have an empty owner, Machina isn't an agent
+fn is_agent(nickname: &str) -> bool {
+    !nickname.is_empty() && nickname != MACHINA_OWNER
+}
+
+/// single agent statistics
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AgentStats {
+    /// agent faction, deduced from the portals the agent has deployed on
+    pub faction: Option<Faction>,
+    /// owned portals count
+    pub portals_owned: usize,
+    /// deployed resonators count, indexed by level
+    pub resonators: [usize; 9],
+    /// deployed mods count, by kind
+    pub mods: HashMap<ModKind, usize>,
+    /// ids of the portals the agent has presence on
+    pub portals: Vec<SmolStr>,
+}
+
+impl AgentStats {
+    /// total deployed resonators
+    pub fn resonators_deployed(&self) -> usize {
+        self.resonators.iter().sum()
+    }
+
+    /// total deployed mods
+    pub fn mods_deployed(&self) -> usize {
+        self.mods.values().sum()
+    }
+
+    fn add_presence(&mut self, portal_id: &SmolStr, faction: Faction) {
+        self.faction.get_or_insert(faction);
+        if !self.portals.contains(portal_id) {
+            self.portals.push(portal_id.clone());
+        }
+    }
+}
+
+/// per-agent statistics over a set of portal details
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AgentsSummary {
+    /// statistics, by agent nickname
+    pub agents: HashMap<SmolStr, AgentStats>,
+}
+
+impl AgentsSummary {
+    /// adds a portal to the summary, Machina portals are ignored
+    pub fn add(&mut self, portal_id: &SmolStr, portal: &IntelPortal) {
+        if portal.faction.is_machina() {
+            return;
+        }
+        if let Some(owner) = portal.owner.as_ref().filter(|owner| is_agent(owner)) {
+            let stats = self.agents.entry(owner.clone()).or_default();
+            stats.portals_owned += 1;
+            stats.add_presence(portal_id, portal.faction);
+        }
+        for resonator in portal.resonators.iter().flatten().filter(|r| is_agent(&r.owner)) {
+            let stats = self.agents.entry(resonator.owner.clone()).or_default();
+            if let Some(count) = stats.resonators.get_mut(usize::from(resonator.level)) {
+                *count += 1;
+            }
+            stats.add_presence(portal_id, portal.faction);
+        }
+        for m in portal.mods.iter().flatten().flatten().filter(|m| is_agent(&m.owner)) {
+            let stats = self.agents.entry(m.owner.clone()).or_default();
+            *stats.mods.entry(m.name.clone()).or_default() += 1;
+            stats.add_presence(portal_id, portal.faction);
+        }
+    }
+
+    /// agents of given faction
+    pub fn by_faction(&self, faction: Faction) -> impl Iterator<Item = (&SmolStr, &AgentStats)> {
+        self.agents.iter().filter(move |(_, stats)| stats.faction == Some(faction))
+    }
+}
+
+impl<'a> FromIterator<(&'a SmolStr, &'a IntelPortal)> for AgentsSummary {
+    fn from_iter<I: IntoIterator<Item = (&'a SmolStr, &'a IntelPortal)>>(iter: I) -> Self {
+        let mut summary = AgentsSummary::default();
+        for (portal_id, portal) in iter {
+            summary.add(portal_id, portal);
+        }
+        summary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use smol_str::SmolStr;
+
+    use crate::entities::{Faction, IntelPortal, ModKind};
+
+    #[test]
+    fn summary() {
+        let first: IntelPortal = serde_json::from_str(r#"["p","R",45599806,12377142,6,100,8,null,"First",[],false,false,null,1720246737675,[["TerminateThis","Portal Shield","COMMON",{"MITIGATION":"30","REMOVAL_STICKINESS":"0"}],["Other","Heat Sink","RARE",{"HACK_SPEED":"500000","REMOVAL_STICKINESS":"0"}],null,null],[["TerminateThis",8,6000],["TerminateThis",7,5000],["Other",6,4000]],"TerminateThis",["","",[]]]"#).unwrap();
+        let second: IntelPortal = serde_json::from_str(r#"["p","R",45599807,12377143,1,100,1,null,"Second",[],false,false,null,1720246737675,[null,null,null,null],[["TerminateThis",1,1000]],"Other",["","",[]]]"#).unwrap();
+        let ids = [SmolStr::from("first.16"), SmolStr::from("second.16")];
+
+        let summary = ids.iter().zip([&first, &second]).collect::<super::AgentsSummary>();
+        let agent = &summary.agents["TerminateThis"];
+        assert_eq!(agent.faction, Some(Faction::Resistance));
+        assert_eq!(agent.portals_owned, 1);
+        assert_eq!(agent.resonators_deployed(), 3);
+        assert_eq!(agent.resonators[8], 1);
+        assert_eq!(agent.mods[&ModKind::PortalShield], 1);
+        assert_eq!(agent.portals, ids);
+
+        let other = &summary.agents["Other"];
+        assert_eq!(other.portals_owned, 1);
+        assert_eq!(other.mods_deployed(), 1);
+        assert_eq!(summary.by_faction(Faction::Resistance).count(), 2);
+    }
+
+    #[test]
+    fn neutral_and_machina() {
+        let neutral: IntelPortal = serde_json::from_str(r#"["p","N",45599078,12341800,1,0,0,null,"Neutral",[],false,false,null,1711348025166,[null,null,null,null],[],"",["","",[]]]"#).unwrap();
+        let machina: IntelPortal = serde_json::from_str(r#"["p","M",45590126,12338500,1,100,1,null,"Machina",[],false,false,null,1720246737675,[["__MACHINA__","SoftBank Ultra Link","VERY_RARE",{"OUTGOING_LINKS_BONUS":"8"}],null,null,null],[["__MACHINA__",1,1000]],"__MACHINA__",["","",[]]]"#).unwrap();
+        let ids = [SmolStr::from("neutral.16"), SmolStr::from("machina.16")];
+
+        let summary = ids.iter().zip([&neutral, &machina]).collect::<super::AgentsSummary>();
+        assert!(summary.agents.is_empty(), "{summary:#?}");
+    }
+}
@@ -0,0 +1,250 @@
+use smol_str::SmolStr;
+
+use crate::{
+    coords::LatLng,
+    plexts::{Markup, Plext, Tab, Team},
+};
+
+/// agent referenced by a COMM message
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CommPlayer {
+    /// nickname
+    pub name: SmolStr,
+    /// faction
+    pub team: Team,
+}
+
+/// portal referenced by a COMM message
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommPortal {
+    /// title
+    pub name: SmolStr,
+    /// address
+    pub address: SmolStr,
+    /// latitude
+    pub latitude: f64,
+    /// longitude
+    pub longitude: f64,
+    /// faction
+    pub team: Team,
+}
+
+impl CommPortal {
+    /// portal coordinates
+    pub fn coords(&self) -> LatLng {
+        LatLng::new(self.latitude, self.longitude)
+    }
+}
+
+/// COMM message decoded from its markup
+#[derive(Clone, Debug, PartialEq)]
+pub enum CommEvent {
+    /// resonator deployed on a portal
+    ResonatorDeployed {
+        /// deploying agent
+        player: CommPlayer,
+        /// target portal
+        portal: CommPortal,
+    },
+    /// resonator destroyed on a portal
+    ResonatorDestroyed {
+        /// attacking agent
+        player: CommPlayer,
+        /// target portal
+        portal: CommPortal,
+    },
+    /// portal captured
+    PortalCaptured {
+        /// capturing agent
+        player: CommPlayer,
+        /// captured portal
+        portal: CommPortal,
+    },
+    /// link created
+    PortalsLinked {
+        /// linking agent
+        player: CommPlayer,
+        /// link origin
+        from: CommPortal,
+        /// link destination
+        to: CommPortal,
+    },
+    /// field created
+    FieldCreated {
+        /// linking agent
+        player: CommPlayer,
+        /// portal the field has been created from
+        portal: CommPortal,
+        /// mind units gained
+        mu: u64,
+    },
+    /// link destroyed
+    LinkDestroyed {
+        /// attacking agent
+        player: CommPlayer,
+        /// link origin
+        from: CommPortal,
+        /// link destination
+        to: CommPortal,
+    },
+    /// field destroyed
+    FieldDestroyed {
+        /// attacking agent
+        player: CommPlayer,
+        /// field anchor
+        portal: CommPortal,
+        /// mind units lost
+        mu: u64,
+    },
+    /// resonator, link or field decayed
+    Decayed {
+        /// decayed portal, or link origin
+        portal: CommPortal,
+        /// link destination
+        destination: Option<CommPortal>,
+        /// mind units lost, for fields
+        mu: Option<u64>,
+    },
+    /// public chat message
+    Chat {
+        /// sending agent
+        sender: CommPlayer,
+        /// message text, without the sender
+        text: SmolStr,
+        /// mentioned agents
+        mentions: Vec<CommPlayer>,
+    },
+    /// faction chat message
+    SecureMessage {
+        /// sending agent
+        sender: CommPlayer,
+        /// message text, without the sender
+        text: SmolStr,
+        /// mentioned agents
+        mentions: Vec<CommPlayer>,
+    },
+    /// chat message mentioning the current agent, as received in the alerts tab
+    Mention {
+        /// sending agent
+        sender: CommPlayer,
+        /// message text, without the sender
+        text: SmolStr,
+        /// faction chat message
+        secure: bool,
+    },
+    /// any other system message
+    SystemBroadcast {
+        /// full message text
+        text: SmolStr,
+        /// referenced agents
+        players: Vec<CommPlayer>,
+        /// referenced portals
+        portals: Vec<CommPortal>,
+    },
+}
+
+fn player(plain: &str, team: &Team) -> CommPlayer {
+    CommPlayer { name: plain.trim_start_matches('@').trim_end_matches(": ").into(), team: team.clone() }
+}
+
+impl Plext {
+    /// decodes the message into a typed event
+    pub fn event(&self) -> CommEvent {
+        let mut players = Vec::new();
+        let mut portals = Vec::new();
+        let mut texts = Vec::new();
+        let mut sender = None;
+        for markup in &self.markup {
+            match markup {
+                Markup::Player { plain, team } | Markup::AtPlayer { plain, team } => players.push(player(plain, team)),
+                Markup::Portal { name, address, lat, lng, team, .. } => portals.push(CommPortal {
+                    name: name.clone(),
+                    address: address.clone(),
+                    latitude: *lat,
+                    longitude: *lng,
+                    team: team.clone(),
+                }),
+                Markup::Text { plain } => texts.push(plain.as_str()),
+                Markup::Sender { plain, team } => sender = Some(player(plain, team)),
+                _ => {}
+            }
+        }
+
+        if let Some(sender) = sender {
+            // message body is everything after the sender markup
+            let text = self
+                .markup
+                .iter()
+                .skip_while(|markup| !matches!(markup, Markup::Sender { .. }))
+                .skip(1)
+                .map(|markup| markup.plain().as_str())
+                .collect::<String>()
+                .trim()
+                .into();
+            return match (self.tab(), self.is_secure()) {
+                (Tab::Alerts, secure) => CommEvent::Mention { sender, text, secure },
+                (_, true) => CommEvent::SecureMessage { sender, text, mentions: players },
+                (_, false) => CommEvent::Chat { sender, text, mentions: players },
+            };
+        }
+
+        let has = |needle: &str| texts.iter().any(|t| t.contains(needle));
+        // MUs are a standalone text element
+        let mu = texts.iter().find_map(|t| t.parse::<u64>().ok());
+        let mut portals_iter = portals.iter().cloned();
+        let first_player = players.first().cloned();
+        let event = match (first_player, portals_iter.next(), portals_iter.next()) {
+            (_, Some(portal), destination) if has("decayed") => Some(CommEvent::Decayed { portal, destination, mu }),
+            (Some(player), Some(portal), _) if has(" deployed a Resonator on ") => {
+                Some(CommEvent::ResonatorDeployed { player, portal })
+            }
+            (Some(player), Some(portal), _) if has(" destroyed a Resonator on ") => {
+                Some(CommEvent::ResonatorDestroyed { player, portal })
+            }
+            (Some(player), Some(portal), _) if has(" captured ") => Some(CommEvent::PortalCaptured { player, portal }),
+            (Some(player), Some(from), Some(to)) if has(" linked ") => {
+                Some(CommEvent::PortalsLinked { player, from, to })
+            }
+            (Some(player), Some(portal), _) if has(" created a Control Field @") => {
+                mu.map(|mu| CommEvent::FieldCreated { player, portal, mu })
+            }
+            (Some(player), Some(from), Some(to)) if has(" destroyed the ") && has(" Link ") => {
+                Some(CommEvent::LinkDestroyed { player, from, to })
+            }
+            (Some(player), Some(portal), _) if has(" destroyed the ") && has(" Control Field @") => {
+                mu.map(|mu| CommEvent::FieldDestroyed { player, portal, mu })
+            }
+            _ => None,
+        };
+        event.unwrap_or_else(|| CommEvent::SystemBroadcast { text: self.text.clone(), players, portals })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommEvent;
+    use crate::{
+        coords::LatLng,
+        entities::Faction,
+        plexts::{IntelResponse, Team},
+    };
+
+    #[test]
+    fn events() {
+        let res: IntelResponse = serde_json::from_str(r#"{"result":[
+            ["a.d",1719762782388,{"plext":{"text":"Resistance agent btgalpi created a Control Field @Campo +12 MUs","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" created a Control Field @"}],["PORTAL",{"plain":"Campo (Via S. Domenico)","name":"Campo","address":"Via S. Domenico","latE6":45385714,"lngE6":11799382,"team":"RESISTANCE"}],["TEXT",{"plain":" +"}],["TEXT",{"plain":"12"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],
+            ["b.d",1719762782388,{"plext":{"text":"Agent btgalpi destroyed the Enlightened Link Campo to Fontana","team":"ENLIGHTENED","markup":[["TEXT",{"plain":"Agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed the "}],["FACTION",{"team":"ENLIGHTENED","plain":"Enlightened"}],["TEXT",{"plain":" Link "}],["PORTAL",{"plain":"Campo (Via S. Domenico)","name":"Campo","address":"Via S. Domenico","latE6":45385714,"lngE6":11799382,"team":"ENLIGHTENED"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Fontana (Via Municipio)","name":"Fontana","address":"Via Municipio","latE6":45406674,"lngE6":11875542,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],
+            ["c.d",1719762782388,{"plext":{"text":"[secure] btgalpi: hi @CeccoMan","team":"RESISTANCE","markup":[["SECURE",{"plain":"[secure] "}],["SENDER",{"plain":"btgalpi: ","team":"RESISTANCE"}],["TEXT",{"plain":"hi "}],["AT_PLAYER",{"plain":"@CeccoMan","team":"RESISTANCE"}]],"plextType":"PLAYER_GENERATED","categories":2}}],
+            ["d.d",1719762782388,{"plext":{"text":"The Link Campo to Fontana has decayed","team":"NEUTRAL","markup":[["TEXT",{"plain":"The Link "}],["PORTAL",{"plain":"Campo (Via S. Domenico)","name":"Campo","address":"Via S. Domenico","latE6":45385714,"lngE6":11799382,"team":"ENLIGHTENED"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Fontana (Via Municipio)","name":"Fontana","address":"Via Municipio","latE6":45406674,"lngE6":11875542,"team":"ENLIGHTENED"}],["TEXT",{"plain":" has decayed"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}]
+        ]}"#).unwrap();
+        let events = res.result.iter().map(|(_, _, wrapper)| wrapper.plext.event()).collect::<Vec<_>>();
+
+        assert!(matches!(&events[0], CommEvent::FieldCreated { player, portal, mu: 12 }
+            if player.name == "btgalpi" && portal.name == "Campo" && portal.coords() == LatLng::new(45.385714, 11.799382)));
+        assert!(matches!(&events[1], CommEvent::LinkDestroyed { player, to, .. }
+            if player.team == Team::Faction(Faction::Resistance) && to.name == "Fontana"));
+        assert!(matches!(&events[2], CommEvent::SecureMessage { sender, text, mentions }
+            if sender.name == "btgalpi" && text == "hi @CeccoMan" && mentions[0].name == "CeccoMan"));
+        assert!(matches!(&events[3], CommEvent::Decayed { destination: Some(_), mu: None, .. }));
+    }
+}
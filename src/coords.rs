@@ -0,0 +1,94 @@
+/// point, in degrees
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct LatLng {
+    /// latitude
+    pub lat: f64,
+    /// longitude
+    pub lng: f64,
+}
+
+impl LatLng {
+    /// creates a point from degrees
+    pub fn new(lat: f64, lng: f64) -> Self {
+        LatLng { lat, lng }
+    }
+
+    /// creates a point from E6 units, as used by Intel
+    pub fn from_e6(lat_e6: i64, lng_e6: i64) -> Self {
+        LatLng { lat: lat_e6 as f64 / 1000000_f64, lng: lng_e6 as f64 / 1000000_f64 }
+    }
+
+    /// latitude in E6 units
+    pub fn lat_e6(&self) -> i64 {
+        (self.lat * 1000000_f64).round() as i64
+    }
+
+    /// longitude in E6 units
+    pub fn lng_e6(&self) -> i64 {
+        (self.lng * 1000000_f64).round() as i64
+    }
+}
+
+impl From<(f64, f64)> for LatLng {
+    fn from((lat, lng): (f64, f64)) -> Self {
+        LatLng { lat, lng }
+    }
+}
+
+impl From<LatLng> for (f64, f64) {
+    fn from(point: LatLng) -> Self {
+        (point.lat, point.lng)
+    }
+}
+
+/// area between two corners
+///
+/// longitudes grow from the south-west corner to the north-east one, so boxes crossing the antimeridian
+/// can't be expressed and have to be split in two
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct BoundingBox {
+    /// south-west corner
+    pub south_west: LatLng,
+    /// north-east corner
+    pub north_east: LatLng,
+}
+
+impl BoundingBox {
+    /// creates a box from two opposite corners, in any order, always taking the side not crossing the antimeridian
+    pub fn new(a: impl Into<LatLng>, b: impl Into<LatLng>) -> Self {
+        let (a, b) = (a.into(), b.into());
+        BoundingBox {
+            south_west: LatLng { lat: a.lat.min(b.lat), lng: a.lng.min(b.lng) },
+            north_east: LatLng { lat: a.lat.max(b.lat), lng: a.lng.max(b.lng) },
+        }
+    }
+
+    /// checks if the point lies inside the box, borders included
+    pub fn contains(&self, point: impl Into<LatLng>) -> bool {
+        let point = point.into();
+        (self.south_west.lat..=self.north_east.lat).contains(&point.lat)
+            && (self.south_west.lng..=self.north_east.lng).contains(&point.lng)
+    }
+}
+
+impl<A: Into<LatLng>, B: Into<LatLng>> From<(A, B)> for BoundingBox {
+    fn from((a, b): (A, B)) -> Self {
+        BoundingBox::new(a, b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BoundingBox, LatLng};
+
+    #[test]
+    fn bounding_box() {
+        // southern and western hemispheres are negative
+        let bbox = BoundingBox::new((-22.9, -43.1), (-23.0, -43.3));
+        assert_eq!(bbox.south_west, LatLng::new(-23.0, -43.3));
+        assert_eq!(bbox.south_west.lat_e6(), -23000000);
+        assert_eq!(bbox.north_east.lng_e6(), -43100000);
+        assert!(bbox.contains(LatLng::from_e6(-22950000, -43200000)));
+        assert!(!bbox.contains((22.95, 43.2)));
+    }
+}
@@ -0,0 +1,290 @@
+use smol_str::SmolStr;
+
+use crate::{
+    entities::{Entity, Faction, IntelEntities, IntelField, IntelLink, IntelMod, IntelPortal, IntelResonator},
+    world::World,
+};
+
+/// change between two snapshots of the same area
+#[derive(Clone, Debug)]
+pub enum Change {
+    /// neutral portal has been captured
+    PortalCaptured {
+        /// portal id
+        id: SmolStr,
+        /// capturing faction
+        faction: Faction,
+        /// new owner, if known
+        owner: Option<SmolStr>,
+    },
+    /// portal has been neutralized, by an enemy or by decay
+    PortalNeutralized {
+        /// portal id
+        id: SmolStr,
+        /// previous faction
+        previous: Faction,
+    },
+    /// portal changed faction without going neutral in between
+    FactionFlipped {
+        /// portal id
+        id: SmolStr,
+        /// previous faction
+        from: Faction,
+        /// current faction
+        to: Faction,
+    },
+    /// portal level changed
+    LevelChanged {
+        /// portal id
+        id: SmolStr,
+        /// previous level
+        from: u8,
+        /// current level
+        to: u8,
+    },
+    /// portal health changed without changing faction, by decay, attacks or recharges
+    HealthChanged {
+        /// portal id
+        id: SmolStr,
+        /// previous health
+        from: u8,
+        /// current health
+        to: u8,
+    },
+    /// resonators have been deployed or upgraded, needs details in both snapshots
+    ResonatorsAdded {
+        /// portal id
+        id: SmolStr,
+        /// new resonators
+        resonators: Vec<IntelResonator>,
+    },
+    /// resonators have been destroyed or replaced, needs details in both snapshots
+    ResonatorsRemoved {
+        /// portal id
+        id: SmolStr,
+        /// removed resonators
+        resonators: Vec<IntelResonator>,
+    },
+    /// mods have been deployed, needs details in both snapshots
+    ModsAdded {
+        /// portal id
+        id: SmolStr,
+        /// new mods
+        mods: Vec<IntelMod>,
+    },
+    /// mods have been destroyed, needs details in both snapshots
+    ModsRemoved {
+        /// portal id
+        id: SmolStr,
+        /// removed mods
+        mods: Vec<IntelMod>,
+    },
+    /// link has been created
+    LinkCreated(Entity<IntelLink>),
+    /// link has been destroyed
+    LinkDestroyed(Entity<IntelLink>),
+    /// field has been created
+    FieldCreated(Entity<IntelField>),
+    /// field has been destroyed
+    FieldDestroyed(Entity<IntelField>),
+}
+
+impl Change {
+    /// id of the changed entity
+    pub fn id(&self) -> &SmolStr {
+        match self {
+            Change::PortalCaptured { id, .. }
+            | Change::PortalNeutralized { id, .. }
+            | Change::FactionFlipped { id, .. }
+            | Change::LevelChanged { id, .. }
+            | Change::HealthChanged { id, .. }
+            | Change::ResonatorsAdded { id, .. }
+            | Change::ResonatorsRemoved { id, .. }
+            | Change::ModsAdded { id, .. }
+            | Change::ModsRemoved { id, .. } => id,
+            Change::LinkCreated(e) | Change::LinkDestroyed(e) => &e.id,
+            Change::FieldCreated(e) | Change::FieldDestroyed(e) => &e.id,
+        }
+    }
+}
+
+/// elements of `a` missing from `b`, compared as multisets
+fn missing<'a, T: Clone, K: PartialEq>(a: &'a [T], b: &'a [T], key: impl Fn(&T) -> K) -> Vec<T> {
+    let mut remaining = b.iter().map(&key).collect::<Vec<_>>();
+    a.iter()
+        .filter(|item| {
+            let k = key(item);
+            match remaining.iter().position(|r| *r == k) {
+                Some(pos) => {
+                    remaining.swap_remove(pos);
+                    false
+                }
+                None => true,
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+fn portal_changes(id: &SmolStr, old: &IntelPortal, new: &IntelPortal, changes: &mut Vec<Change>) {
+    match (old.faction, new.faction) {
+        (from, to) if from == to => {}
+        (Faction::Neutral, faction) => {
+            changes.push(Change::PortalCaptured { id: id.clone(), faction, owner: new.owner.clone() })
+        }
+        (previous, Faction::Neutral) => changes.push(Change::PortalNeutralized { id: id.clone(), previous }),
+        (from, to) => changes.push(Change::FactionFlipped { id: id.clone(), from, to }),
+    }
+    if old.level != new.level {
+        changes.push(Change::LevelChanged { id: id.clone(), from: old.level, to: new.level });
+    }
+    if old.faction == new.faction && old.health != new.health {
+        changes.push(Change::HealthChanged { id: id.clone(), from: old.health, to: new.health });
+    }
+
+    if let (Some(old), Some(new)) = (&old.resonators, &new.resonators) {
+        let key = |r: &IntelResonator| (r.owner.clone(), r.level);
+        let added = missing(new, old, key);
+        if !added.is_empty() {
+            changes.push(Change::ResonatorsAdded { id: id.clone(), resonators: added });
+        }
+        let removed = missing(old, new, key);
+        if !removed.is_empty() {
+            changes.push(Change::ResonatorsRemoved { id: id.clone(), resonators: removed });
+        }
+    }
+
+    if let (Some(old), Some(new)) = (&old.mods, &new.mods) {
+        let (old, new) =
+            (old.iter().flatten().cloned().collect::<Vec<_>>(), new.iter().flatten().cloned().collect::<Vec<_>>());
+        let key = |m: &IntelMod| (m.owner.clone(), m.name.clone(), m.rarity.clone());
+        let added = missing(&new, &old, key);
+        if !added.is_empty() {
+            changes.push(Change::ModsAdded { id: id.clone(), mods: added });
+        }
+        let removed = missing(&old, &new, key);
+        if !removed.is_empty() {
+            changes.push(Change::ModsRemoved { id: id.clone(), mods: removed });
+        }
+    }
+}
+
+impl World {
+    /// changes from this snapshot to a newer one of the same area, sorted by entity id
+    ///
+    /// portals missing from one of the snapshots are ignored, since they're probably out of the scanned area,
+    /// links and fields missing from the newer one are reported as destroyed, so a long lived world must be
+    /// kept current with `World::replace_area`, plain ingestion never forgets anything
+    pub fn diff(&self, newer: &World) -> Vec<Change> {
+        let mut changes = Vec::new();
+
+        for (id, old) in self.portals() {
+            if let Some(new) = newer.portal(id) {
+                portal_changes(id, &old.portal, &new.portal, &mut changes);
+            }
+        }
+
+        changes.extend(newer.links().filter(|l| self.link(&l.id).is_none()).cloned().map(Change::LinkCreated));
+        changes.extend(self.links().filter(|l| newer.link(&l.id).is_none()).cloned().map(Change::LinkDestroyed));
+        changes.extend(newer.fields().filter(|f| self.field(&f.id).is_none()).cloned().map(Change::FieldCreated));
+        changes.extend(self.fields().filter(|f| newer.field(&f.id).is_none()).cloned().map(Change::FieldDestroyed));
+
+        changes.sort_by(|a, b| a.id().cmp(b.id()));
+        changes
+    }
+}
+
+/// changes between two scan results of the same area
+pub fn diff_scans<I, J>(old: I, new: J) -> Vec<Change>
+where
+    I: IntoIterator<Item = IntelEntities>,
+    J: IntoIterator<Item = IntelEntities>,
+{
+    let mut old_world = World::default();
+    old_world.extend(old);
+    let mut new_world = World::default();
+    new_world.extend(new);
+    old_world.diff(&new_world)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Change;
+    use crate::{entities::Faction, utils::entities};
+
+    #[test]
+    fn diff_scans() {
+        let old = entities(
+            r#"[
+                ["a.16",1000,["p","N",45000000,12000000,1,0,0,null,"A",[],false,false,null,1000,null,null,null,null,0]],
+                ["b.16",1000,["p","R",45010000,12010000,7,100,8,null,"B",[],false,false,null,1000,null,null,null,null,0]],
+                ["c.16",1000,["p","R",45010000,12000000,7,100,8,null,"C",[],false,false,null,1000,null,null,null,null,0]],
+                ["x.9",1000,["e","R","b.16",45010000,12010000,"c.16",45010000,12000000]]
+            ]"#,
+        );
+        let new = entities(
+            r#"[
+                ["a.16",2000,["p","E",45000000,12000000,1,100,1,null,"A",[],false,false,null,2000,null,null,null,null,0]],
+                ["b.16",2000,["p","N",45010000,12010000,1,0,0,null,"B",[],false,false,null,2000,null,null,null,null,0]],
+                ["c.16",1000,["p","R",45010000,12000000,7,100,8,null,"C",[],false,false,null,1000,null,null,null,null,0]]
+            ]"#,
+        );
+        let changes = super::diff_scans([old], [new]);
+        assert!(
+            matches!(&changes[0], Change::PortalCaptured { id, faction: Faction::Enlightened, .. } if id == "a.16")
+        );
+        assert!(matches!(&changes[1], Change::PortalNeutralized { id, previous: Faction::Resistance } if id == "b.16"));
+        assert!(matches!(&changes[2], Change::LevelChanged { from: 7, to: 1, .. }));
+        assert!(matches!(&changes[3], Change::LinkDestroyed(link) if link.id == "x.9"));
+        assert_eq!(changes.len(), 4);
+    }
+
+    #[test]
+    fn diff_rescan() {
+        let bbox = ((44.9, 11.9), (45.1, 12.1)).into();
+        let mut world = crate::world::World::default();
+        world.replace_area(
+            bbox,
+            None,
+            None,
+            None,
+            [entities(
+                r#"[
+                    ["b.16",1000,["p","R",45010000,12010000,7,100,8,null,"B",[],false,false,null,1000,null,null,null,null,0]],
+                    ["c.16",1000,["p","R",45010000,12000000,7,100,8,null,"C",[],false,false,null,1000,null,null,null,null,0]],
+                    ["x.9",1000,["e","R","b.16",45010000,12010000,"c.16",45010000,12000000]]
+                ]"#,
+            )],
+        );
+        let old = world.clone();
+        world.replace_area(
+            bbox,
+            None,
+            None,
+            None,
+            [entities(
+                r#"[
+                    ["b.16",2000,["p","R",45010000,12010000,7,60,8,null,"B",[],false,false,null,1000,null,null,null,null,0]],
+                    ["c.16",1000,["p","R",45010000,12000000,7,100,8,null,"C",[],false,false,null,1000,null,null,null,null,0]]
+                ]"#,
+            )],
+        );
+        let changes = old.diff(&world);
+        assert!(matches!(&changes[0], Change::HealthChanged { from: 100, to: 60, .. }));
+        assert!(matches!(&changes[1], Change::LinkDestroyed(link) if link.id == "x.9"));
+        assert_eq!(changes.len(), 2);
+    }
+
+    #[test]
+    fn diff_details() {
+        let mut old = crate::world::World::default();
+        old.ingest_portal_details("a.16", serde_json::from_str(r#"{"result":["p","R",45000000,12000000,1,100,1,null,"A",[],false,false,null,1000,[["TerminateThis","Portal Shield","COMMON",{"MITIGATION":"30"}],null,null,null],[["TerminateThis",5,2550]],"TerminateThis",["","",[]]]}"#).unwrap());
+        let mut new = crate::world::World::default();
+        new.ingest_portal_details("a.16", serde_json::from_str(r#"{"result":["p","R",45000000,12000000,1,100,2,null,"A",[],false,false,null,2000,[null,null,null,null],[["TerminateThis",5,2550],["Other",8,6000]],"TerminateThis",["","",[]]]}"#).unwrap());
+
+        let changes = old.diff(&new);
+        assert!(matches!(&changes[0], Change::ResonatorsAdded { resonators, .. } if resonators[0].owner == "Other"));
+        assert!(matches!(&changes[1], Change::ModsRemoved { mods, .. } if mods.len() == 1));
+        assert_eq!(changes.len(), 2);
+    }
+}
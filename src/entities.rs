@@ -5,6 +5,14 @@ use serde_json::value::Value;
 use smol_str::SmolStr;
 use tracing::warn;
 
+use crate::{coords::LatLng, geo};
+
+static RESONATOR_MAX_ENERGY: [u16; 9] = [0, 1000, 1500, 2000, 2500, 3000, 4000, 5000, 6000];
+static LINK_AMP_FACTORS: [f64; 4] = [1_f64, 0.25, 0.125, 0.125];
+const MAX_MITIGATION: u32 = 95;
+// resonators lose 15% of their max XM every day
+const DECAY_RATE: f64 = 0.15;
+
 /// endpoint reponse root
 #[derive(Debug, Deserialize)]
 pub struct IntelResponse {
@@ -54,6 +62,13 @@ pub struct IntelEntities {
     pub entities: Vec<IntelEntity>,
 }
 
+impl IntelEntities {
+    /// counts logged in agent uniques over contained portals
+    pub fn uniques(&self) -> Uniques {
+        self.entities.iter().filter_map(IntelEntity::as_portal).map(|p| &p.entity).collect()
+    }
+}
+
 /// endpoint main entity
 /// values vary based on type
 #[derive(Clone, Debug, Deserialize)]
@@ -180,13 +195,13 @@ pub struct IntelPortal {
     /// title
     pub title: SmolStr,
     /// ornaments
-    pub ornaments: Vec<Value>,
+    pub ornaments: Vec<Ornament>,
     /// mission
     pub mission: bool,
     /// mission_50plus
     pub mission_50plus: bool,
     /// artifact_brief
-    pub artifact_brief: Value,
+    pub artifact_brief: Option<ArtifactBrief>,
     /// timestamp
     pub timestamp: i64,
     /// mods
@@ -198,12 +213,168 @@ pub struct IntelPortal {
     /// owner
     #[serde(default)]
     pub owner: Option<SmolStr>,
-    /// artifact_detail
-    #[serde(default)]
-    pub artifact_detail: Option<Vec<Value>>,
+    /// artifact_detail, `None` for portals without artifacts
+    #[serde(default, deserialize_with = "deserialize_artifact_detail")]
+    pub artifact_detail: Option<ArtifactDetail>,
     /// history
     #[serde(default)]
-    pub history: Value,
+    pub history: Option<PortalHistory>,
+}
+
+impl IntelPortal {
+    /// coordinates
+    pub fn coords(&self) -> LatLng {
+        LatLng::new(self.latitude, self.longitude)
+    }
+
+    /// checks if portal is an anomaly portal
+    pub fn is_anomaly(&self) -> bool {
+        self.ornaments.iter().any(Ornament::is_anomaly)
+    }
+
+    /// checks if portal has a battle beacon deployed
+    pub fn has_battle_beacon(&self) -> bool {
+        self.ornaments.iter().any(Ornament::is_battle_beacon)
+    }
+
+    /// checks if portal is a shard target
+    pub fn is_shard_target(&self) -> bool {
+        self.artifact_brief.as_ref().is_some_and(|brief| !brief.targets.is_empty())
+    }
+
+    /// checks if portal holds shards
+    pub fn has_shards(&self) -> bool {
+        self.artifact_brief.as_ref().is_some_and(|brief| !brief.fragments.is_empty())
+    }
+
+    fn deployed_mods(&self) -> impl Iterator<Item = &IntelMod> {
+        self.mods.iter().flatten().flatten()
+    }
+
+    /// current XM, available with portal details only
+    pub fn total_energy(&self) -> Option<u32> {
+        Some(self.resonators.as_ref()?.iter().map(|r| u32::from(r.energy)).sum())
+    }
+
+    /// max XM, available with portal details only
+    pub fn max_energy(&self) -> Option<u32> {
+        Some(self.resonators.as_ref()?.iter().map(|r| u32::from(r.max_energy())).sum())
+    }
+
+    /// health percentage computed from resonators, to be cross-checked with `health`
+    pub fn computed_health(&self) -> Option<u8> {
+        let max = self.max_energy()?;
+        if max == 0 {
+            return Some(0);
+        }
+        u8::try_from(self.total_energy()? * 100 / max).ok()
+    }
+
+    /// link range in meters, available with portal details only
+    pub fn link_range(&self) -> Option<f64> {
+        let resonators = self.resonators.as_ref()?;
+        if resonators.len() < 8 {
+            return Some(0_f64);
+        }
+        let average_level = resonators.iter().map(|r| f64::from(r.level)).sum::<f64>() / 8_f64;
+        Some(160_f64 * average_level.powi(4) * self.link_amp_boost())
+    }
+
+    /// link range multiplier given by link amps, first one counts fully, others are reduced
+    pub fn link_amp_boost(&self) -> f64 {
+        let mut multipliers = self
+            .deployed_mods()
+            .filter_map(|m| m.stats.link_range_multiplier)
+            .map(|m| f64::from(m) / 1000_f64)
+            .collect::<Vec<_>>();
+        if multipliers.is_empty() {
+            return 1_f64;
+        }
+        multipliers.sort_unstable_by(|a, b| b.total_cmp(a));
+        multipliers.iter().zip(LINK_AMP_FACTORS).map(|(multiplier, factor)| multiplier * factor).sum()
+    }
+
+    /// portal mitigation, given the number of links attached to the portal
+    pub fn mitigation(&self, link_count: usize) -> Mitigation {
+        let shields = self.deployed_mods().filter_map(|m| m.stats.mitigation).sum::<u32>();
+        let link_defense_boost = self
+            .deployed_mods()
+            .filter_map(|m| m.stats.link_defense_boost)
+            .map(|b| f64::from(b) / 1000_f64)
+            .product::<f64>();
+        let links_mitigation = (400_f64 / 9_f64 * (link_count as f64 / std::f64::consts::E).atan()).round();
+        let links = (links_mitigation * link_defense_boost).round() as u32;
+        let total = (shields + links).min(MAX_MITIGATION);
+        Mitigation { shields, links, total, excess: shields + links - total }
+    }
+
+    /// estimated days until the portal decays to neutral, without recharges
+    pub fn days_to_decay(&self) -> Option<u32> {
+        self.resonators
+            .as_ref()?
+            .iter()
+            .filter(|r| r.max_energy() > 0)
+            .map(|r| (f64::from(r.energy) / (f64::from(r.max_energy()) * DECAY_RATE)).ceil() as u32)
+            .max()
+            .or(Some(0))
+    }
+}
+
+/// portal mitigation breakdown
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Mitigation {
+    /// mitigation given by shields
+    pub shields: u32,
+    /// mitigation given by links
+    pub links: u32,
+    /// effective mitigation, capped
+    pub total: u32,
+    /// mitigation exceeding the cap
+    pub excess: u32,
+}
+
+/// logged in agent history on a portal, decoded from Intel bitfield
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "u64")]
+pub struct PortalHistory {
+    /// portal has been visited
+    pub visited: bool,
+    /// portal has been captured
+    pub captured: bool,
+    /// portal has been scout controlled
+    pub scout_controlled: bool,
+}
+
+// bits this crate doesn't know about are ignored
+impl From<u64> for PortalHistory {
+    fn from(bits: u64) -> Self {
+        PortalHistory { visited: bits & 1 != 0, captured: bits & 2 != 0, scout_controlled: bits & 4 != 0 }
+    }
+}
+
+/// uniques summary over a set of portals
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Uniques {
+    /// portals with history informations
+    pub portals: usize,
+    /// visited portals
+    pub visited: usize,
+    /// captured portals
+    pub captured: usize,
+    /// scout controlled portals
+    pub scout_controlled: usize,
+}
+
+impl<'a> FromIterator<&'a IntelPortal> for Uniques {
+    fn from_iter<I: IntoIterator<Item = &'a IntelPortal>>(iter: I) -> Self {
+        iter.into_iter().filter_map(|p| p.history).fold(Uniques::default(), |mut uniques, history| {
+            uniques.portals += 1;
+            uniques.visited += usize::from(history.visited);
+            uniques.captured += usize::from(history.captured);
+            uniques.scout_controlled += usize::from(history.scout_controlled);
+            uniques
+        })
+    }
 }
 
 /// needed for untagged enum
@@ -213,17 +384,334 @@ enum PortalTag {
     P,
 }
 
+/// portal ornament, categorized by id
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "SmolStr")]
+pub enum Ornament {
+    /// anomaly marker, ids starting with "ap"
+    Anomaly(SmolStr),
+    /// battle beacon, ids starting with "peBB"
+    BattleBeacon(SmolStr),
+    /// battle beacon result, ids starting with "peBR"
+    BattleResult(SmolStr),
+    /// fireworks, ids starting with "peFW"
+    Fireworks(SmolStr),
+    /// any other beacon, ids starting with "pe"
+    Beacon(SmolStr),
+    /// scout controller marker, ids like "sc5_p"
+    ScoutController(SmolStr),
+    /// anything else
+    Unknown(SmolStr),
+}
+
+impl From<SmolStr> for Ornament {
+    fn from(id: SmolStr) -> Self {
+        if id.starts_with("peBB") {
+            Ornament::BattleBeacon(id)
+        } else if id.starts_with("peBR") {
+            Ornament::BattleResult(id)
+        } else if id.starts_with("peFW") {
+            Ornament::Fireworks(id)
+        } else if id.starts_with("pe") {
+            Ornament::Beacon(id)
+        } else if id.starts_with("ap") {
+            Ornament::Anomaly(id)
+        } else if id.starts_with("sc") && id.ends_with("_p") {
+            Ornament::ScoutController(id)
+        } else {
+            Ornament::Unknown(id)
+        }
+    }
+}
+
+impl Ornament {
+    /// ornament id
+    pub fn id(&self) -> &str {
+        match self {
+            Ornament::Anomaly(id)
+            | Ornament::BattleBeacon(id)
+            | Ornament::BattleResult(id)
+            | Ornament::Fireworks(id)
+            | Ornament::Beacon(id)
+            | Ornament::ScoutController(id)
+            | Ornament::Unknown(id) => id,
+        }
+    }
+
+    /// checks if anomaly marker
+    pub fn is_anomaly(&self) -> bool {
+        matches!(self, Ornament::Anomaly(_))
+    }
+
+    /// checks if battle beacon
+    pub fn is_battle_beacon(&self) -> bool {
+        matches!(self, Ornament::BattleBeacon(_))
+    }
+
+    /// checks if any kind of beacon
+    pub fn is_beacon(&self) -> bool {
+        matches!(
+            self,
+            Ornament::BattleBeacon(_) | Ornament::BattleResult(_) | Ornament::Fireworks(_) | Ornament::Beacon(_)
+        )
+    }
+}
+
+/// artifacts summary, made of ([[type, ...], ...], [[type, ...], ...])
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(from = "Value")]
+pub struct ArtifactBrief {
+    /// artifact types whose fragments are on the portal
+    pub fragments: Vec<SmolStr>,
+    /// artifact types targeting the portal
+    pub targets: Vec<SmolStr>,
+    /// raw value, when it doesn't match the known layout
+    pub other: Option<Value>,
+}
+
+impl From<Value> for ArtifactBrief {
+    fn from(value: Value) -> Self {
+        fn types(value: Option<&Value>) -> Option<Vec<SmolStr>> {
+            value?.as_array()?.iter().map(|a| a.as_array()?.first()?.as_str().map(SmolStr::from)).collect()
+        }
+
+        let parsed = value.as_array().and_then(|a| Some((types(a.first())?, types(a.get(1))?)));
+        match parsed {
+            Some((fragments, targets)) => ArtifactBrief { fragments, targets, other: None },
+            None => {
+                warn!("Unmapped artifact brief: {value}");
+                ArtifactBrief { other: Some(value), ..Default::default() }
+            }
+        }
+    }
+}
+
+/// artifact details, made of (type, display name, fragments)
+#[derive(Clone, Debug, Default, PartialEq, Deserialize)]
+#[serde(from = "Value")]
+pub struct ArtifactDetail {
+    /// artifact type
+    pub kind: SmolStr,
+    /// artifact display name
+    pub display_name: SmolStr,
+    /// fragment numbers
+    pub fragments: Vec<u32>,
+    /// raw value, when it doesn't match the known layout
+    pub other: Option<Value>,
+}
+
+impl ArtifactDetail {
+    /// Intel sends an empty placeholder for portals without artifacts
+    pub fn is_empty(&self) -> bool {
+        self.kind.is_empty() && self.fragments.is_empty() && self.other.is_none()
+    }
+}
+
+fn deserialize_artifact_detail<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<ArtifactDetail>, D::Error> {
+    Ok(Option::<ArtifactDetail>::deserialize(deserializer)?.filter(|detail| !detail.is_empty()))
+}
+
+impl From<Value> for ArtifactDetail {
+    fn from(value: Value) -> Self {
+        let parsed = value.as_array().and_then(|a| {
+            let fragments = a.get(2)?.as_array()?.iter().map(|f| f.as_u64()?.try_into().ok()).collect::<Option<_>>()?;
+            Some((a.first()?.as_str()?, a.get(1)?.as_str()?, fragments))
+        });
+        match parsed {
+            Some((kind, display_name, fragments)) => {
+                ArtifactDetail { kind: kind.into(), display_name: display_name.into(), fragments, other: None }
+            }
+            None => {
+                warn!("Unmapped artifact detail: {value}");
+                ArtifactDetail { other: Some(value), ..Default::default() }
+            }
+        }
+    }
+}
+
 /// portal deployed mod
 #[derive(Clone, Debug, Deserialize)]
 pub struct IntelMod {
     /// mod owner
     pub owner: SmolStr,
     /// mod name
-    pub name: SmolStr,
+    pub name: ModKind,
     /// mod rarity
-    pub rarity: SmolStr,
+    pub rarity: Rarity,
     /// mod stats
-    pub stats: Value,
+    pub stats: ModStats,
+}
+
+/// mod kinds
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "SmolStr")]
+pub enum ModKind {
+    /// Portal Shield
+    PortalShield,
+    /// AXA Shield
+    AxaShield,
+    /// Aegis Shield
+    AegisShield,
+    /// Heat Sink
+    HeatSink,
+    /// Multi-hack
+    MultiHack,
+    /// Force Amp
+    ForceAmp,
+    /// Turret
+    Turret,
+    /// Link Amp
+    LinkAmp,
+    /// SoftBank Ultra Link
+    SoftBankUltraLink,
+    /// Ito En Transmuter (+)
+    ItoEnTransmuterPlus,
+    /// Ito En Transmuter (-)
+    ItoEnTransmuterMinus,
+    /// anything else
+    Other(SmolStr),
+}
+
+impl From<SmolStr> for ModKind {
+    fn from(name: SmolStr) -> Self {
+        match name.as_str() {
+            "Portal Shield" => ModKind::PortalShield,
+            "AXA Shield" => ModKind::AxaShield,
+            "Aegis Shield" => ModKind::AegisShield,
+            "Heat Sink" => ModKind::HeatSink,
+            "Multi-hack" => ModKind::MultiHack,
+            "Force Amp" => ModKind::ForceAmp,
+            "Turret" => ModKind::Turret,
+            "Link Amp" => ModKind::LinkAmp,
+            "SoftBank Ultra Link" => ModKind::SoftBankUltraLink,
+            "Ito En Transmuter (+)" => ModKind::ItoEnTransmuterPlus,
+            "Ito En Transmuter (-)" => ModKind::ItoEnTransmuterMinus,
+            _ => ModKind::Other(name),
+        }
+    }
+}
+
+impl ModKind {
+    /// mod name as displayed by Intel
+    pub fn as_str(&self) -> &str {
+        match self {
+            ModKind::PortalShield => "Portal Shield",
+            ModKind::AxaShield => "AXA Shield",
+            ModKind::AegisShield => "Aegis Shield",
+            ModKind::HeatSink => "Heat Sink",
+            ModKind::MultiHack => "Multi-hack",
+            ModKind::ForceAmp => "Force Amp",
+            ModKind::Turret => "Turret",
+            ModKind::LinkAmp => "Link Amp",
+            ModKind::SoftBankUltraLink => "SoftBank Ultra Link",
+            ModKind::ItoEnTransmuterPlus => "Ito En Transmuter (+)",
+            ModKind::ItoEnTransmuterMinus => "Ito En Transmuter (-)",
+            ModKind::Other(name) => name,
+        }
+    }
+
+    /// checks if shield
+    pub fn is_shield(&self) -> bool {
+        matches!(self, ModKind::PortalShield | ModKind::AxaShield | ModKind::AegisShield)
+    }
+
+    /// checks if link amplifier
+    pub fn is_link_amp(&self) -> bool {
+        matches!(self, ModKind::LinkAmp | ModKind::SoftBankUltraLink)
+    }
+}
+
+/// mod rarity
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
+#[serde(from = "SmolStr")]
+pub enum Rarity {
+    /// Common
+    Common,
+    /// Rare
+    Rare,
+    /// Very Rare
+    VeryRare,
+    /// anything else
+    Other(SmolStr),
+}
+
+impl From<SmolStr> for Rarity {
+    fn from(rarity: SmolStr) -> Self {
+        match rarity.as_str() {
+            "COMMON" => Rarity::Common,
+            "RARE" => Rarity::Rare,
+            "VERY_RARE" => Rarity::VeryRare,
+            _ => Rarity::Other(rarity),
+        }
+    }
+}
+
+/// mod stats, values are kept in Intel's own scale
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "HashMap<SmolStr, Value>")]
+pub struct ModStats {
+    /// shield mitigation, in percentage points
+    pub mitigation: Option<u32>,
+    /// removal stickiness, 1000000 means 100%
+    pub removal_stickiness: Option<u32>,
+    /// hack speed, 1000000 means 100%
+    pub hack_speed: Option<u32>,
+    /// burnout insulation, additional hacks
+    pub burnout_insulation: Option<u32>,
+    /// link range multiplier, 1000 means 1x
+    pub link_range_multiplier: Option<u32>,
+    /// link defense boost, 1000 means 1x
+    pub link_defense_boost: Option<u32>,
+    /// additional outgoing links
+    pub outgoing_links_bonus: Option<u32>,
+    /// force amplifier, 1000 means 1x
+    pub force_amplifier: Option<u32>,
+    /// turret attack frequency, 1000 means 1x
+    pub attack_frequency: Option<u32>,
+    /// turret hit bonus, 1000000 means 100%
+    pub hit_bonus: Option<u32>,
+    /// anything else
+    pub other: HashMap<SmolStr, Value>,
+}
+
+impl From<HashMap<SmolStr, Value>> for ModStats {
+    fn from(map: HashMap<SmolStr, Value>) -> Self {
+        let mut stats = ModStats::default();
+        for (key, value) in map {
+            let field = match key.as_str() {
+                "MITIGATION" => &mut stats.mitigation,
+                "REMOVAL_STICKINESS" => &mut stats.removal_stickiness,
+                "HACK_SPEED" => &mut stats.hack_speed,
+                "BURNOUT_INSULATION" => &mut stats.burnout_insulation,
+                "LINK_RANGE_MULTIPLIER" => &mut stats.link_range_multiplier,
+                "LINK_DEFENSE_BOOST" => &mut stats.link_defense_boost,
+                "OUTGOING_LINKS_BONUS" => &mut stats.outgoing_links_bonus,
+                "FORCE_AMPLIFIER" => &mut stats.force_amplifier,
+                "ATTACK_FREQUENCY" => &mut stats.attack_frequency,
+                "HIT_BONUS" => &mut stats.hit_bonus,
+                _ => {
+                    stats.other.insert(key, value);
+                    continue;
+                }
+            };
+            // Intel serializes stats as strings
+            let parsed = match &value {
+                Value::String(s) => s.parse().ok(),
+                Value::Number(n) => n.as_u64().and_then(|n| u32::try_from(n).ok()),
+                _ => None,
+            };
+            match parsed {
+                Some(parsed) => *field = Some(parsed),
+                None => {
+                    warn!("Unparsable mod stat {key}: {value}");
+                    stats.other.insert(key, value);
+                }
+            }
+        }
+        stats
+    }
 }
 
 /// portal deployed resonator
@@ -237,6 +725,13 @@ pub struct IntelResonator {
     pub energy: u16,
 }
 
+impl IntelResonator {
+    /// max XM for resonator level
+    pub fn max_energy(&self) -> u16 {
+        RESONATOR_MAX_ENERGY.get(usize::from(self.level)).copied().unwrap_or_default()
+    }
+}
+
 /// field representation
 #[derive(Clone, Debug, Deserialize)]
 pub struct IntelLink {
@@ -263,6 +758,32 @@ pub struct IntelLink {
     pub destination_longitude: f64,
 }
 
+impl IntelLink {
+    /// origin coordinates
+    pub fn origin(&self) -> LatLng {
+        LatLng::new(self.origin_latitude, self.origin_longitude)
+    }
+
+    /// destination coordinates
+    pub fn destination(&self) -> LatLng {
+        LatLng::new(self.destination_latitude, self.destination_longitude)
+    }
+
+    /// great-circle length, in meters
+    pub fn length(&self) -> f64 {
+        geo::distance(self.origin(), self.destination())
+    }
+
+    /// checks if this link crosses another one, links sharing a portal never cross
+    pub fn crosses(&self, other: &IntelLink) -> bool {
+        let portals = [&self.origin_portal_id, &self.destination_portal_id];
+        if portals.contains(&&other.origin_portal_id) || portals.contains(&&other.destination_portal_id) {
+            return false;
+        }
+        geo::segments_cross(self.origin(), self.destination(), other.origin(), other.destination())
+    }
+}
+
 /// needed for untagged enum
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -282,6 +803,35 @@ pub struct IntelField {
     pub portals: [FieldPortal; 3],
 }
 
+impl IntelField {
+    fn vertices(&self) -> [LatLng; 3] {
+        [self.portals[0].coords(), self.portals[1].coords(), self.portals[2].coords()]
+    }
+
+    /// spherical area, in square meters
+    pub fn area(&self) -> f64 {
+        let [a, b, c] = self.vertices();
+        geo::triangle_area(a, b, c)
+    }
+
+    /// centroid coordinates
+    pub fn centroid(&self) -> LatLng {
+        let [a, b, c] = self.vertices();
+        geo::triangle_centroid(a, b, c)
+    }
+
+    /// checks if a point lies inside the field, vertices excluded
+    pub fn contains(&self, point: impl Into<LatLng>) -> bool {
+        let [a, b, c] = self.vertices();
+        geo::triangle_contains(a, b, c, point.into())
+    }
+
+    /// checks if the field covers given portal, field anchors aren't covered
+    pub fn covers(&self, portal_id: &str, portal: &IntelPortal) -> bool {
+        !self.portals.iter().any(|p| p.id == portal_id) && self.contains(portal.coords())
+    }
+}
+
 /// needed for untagged enum
 #[derive(Clone, Debug, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -302,20 +852,27 @@ pub struct FieldPortal {
     pub longitude: f64,
 }
 
+impl FieldPortal {
+    /// coordinates
+    pub fn coords(&self) -> LatLng {
+        LatLng::new(self.latitude, self.longitude)
+    }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Deserialize)]
 /// Factions
 pub enum Faction {
     /// Neutral
-    #[serde(rename = "N")]
+    #[serde(rename = "N", alias = "NEUTRAL")]
     Neutral,
     /// Enlightened
-    #[serde(rename = "E")]
+    #[serde(rename = "E", alias = "ENLIGHTENED")]
     Enlightened,
     /// Resistance
-    #[serde(rename = "R")]
+    #[serde(rename = "R", alias = "RESISTANCE")]
     Resistance,
     /// Machina
-    #[serde(rename = "M")]
+    #[serde(rename = "M", alias = "MACHINA")]
     Machina,
 }
 
@@ -354,4 +911,13 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn field_contains() {
+        let field: super::Entity<super::IntelField> =
+            serde_json::from_str(r#"["y.b",1000,["r","E",[["a.16",0,0],["b.16",0,1000000],["c.16",1000000,0]]]]"#)
+                .unwrap();
+        assert!(field.entity.contains((0.33, 0.33)));
+        assert!(!field.entity.contains((-0.33, -179.67)));
+    }
 }
@@ -0,0 +1,179 @@
+use std::ops::{Add, Neg, Sub};
+
+use crate::coords::LatLng;
+
+/// mean earth radius, in meters
+pub(crate) const EARTH_RADIUS: f64 = 6371008.8;
+
+// below this threshold two points are considered the same
+const EPSILON: f64 = 1e-12;
+
+/// point on the unit sphere
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub(crate) struct Vector {
+    x: f64,
+    y: f64,
+    z: f64,
+}
+
+impl Vector {
+    pub(crate) fn from_coords(point: LatLng) -> Self {
+        let (lat, lng) = (point.lat.to_radians(), point.lng.to_radians());
+        Vector { x: lat.cos() * lng.cos(), y: lat.cos() * lng.sin(), z: lat.sin() }
+    }
+
+    pub(crate) fn to_coords(self) -> LatLng {
+        let v = self.normalize();
+        LatLng::new(v.z.asin().to_degrees(), v.y.atan2(v.x).to_degrees())
+    }
+
+    fn dot(self, other: Self) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    fn cross(self, other: Self) -> Self {
+        Vector {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    fn norm(self) -> f64 {
+        self.dot(self).sqrt()
+    }
+
+    fn normalize(self) -> Self {
+        let norm = self.norm();
+        Vector { x: self.x / norm, y: self.y / norm, z: self.z / norm }
+    }
+
+    fn same(self, other: Self) -> bool {
+        (self - other).norm() < EPSILON
+    }
+}
+
+impl Add for Vector {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Vector { x: self.x + other.x, y: self.y + other.y, z: self.z + other.z }
+    }
+}
+
+impl Neg for Vector {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Vector { x: -self.x, y: -self.y, z: -self.z }
+    }
+}
+
+impl Sub for Vector {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Vector { x: self.x - other.x, y: self.y - other.y, z: self.z - other.z }
+    }
+}
+
+/// great-circle distance, in meters
+pub(crate) fn distance(from: LatLng, to: LatLng) -> f64 {
+    let (a, b) = (Vector::from_coords(from), Vector::from_coords(to));
+    a.cross(b).norm().atan2(a.dot(b)) * EARTH_RADIUS
+}
+
+/// initial bearing, in degrees clockwise from north
+pub(crate) fn bearing(from: LatLng, to: LatLng) -> f64 {
+    let (lat1, lat2) = (from.lat.to_radians(), to.lat.to_radians());
+    let delta = (to.lng - from.lng).to_radians();
+    let y = delta.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * delta.cos();
+    (y.atan2(x).to_degrees() + 360_f64) % 360_f64
+}
+
+/// positive if point lies at the left of the great circle from a to b, negative if at the right
+pub(crate) fn side(a: LatLng, b: LatLng, point: LatLng) -> f64 {
+    Vector::from_coords(point).dot(Vector::from_coords(a).cross(Vector::from_coords(b)))
+}
+
+/// spherical triangle area, in square meters
+pub(crate) fn triangle_area(a: LatLng, b: LatLng, c: LatLng) -> f64 {
+    let (a, b, c) = (Vector::from_coords(a), Vector::from_coords(b), Vector::from_coords(c));
+    // Van Oosterom and Strackee formula for the spherical excess
+    let excess = 2_f64 * a.dot(b.cross(c)).abs().atan2(1_f64 + a.dot(b) + b.dot(c) + c.dot(a));
+    excess * EARTH_RADIUS * EARTH_RADIUS
+}
+
+/// spherical triangle centroid
+pub(crate) fn triangle_centroid(a: LatLng, b: LatLng, c: LatLng) -> LatLng {
+    (Vector::from_coords(a) + Vector::from_coords(b) + Vector::from_coords(c)).to_coords()
+}
+
+/// checks if point lies strictly inside the spherical triangle, vertices excluded
+pub(crate) fn triangle_contains(a: LatLng, b: LatLng, c: LatLng, point: LatLng) -> bool {
+    let (a, b, c, p) =
+        (Vector::from_coords(a), Vector::from_coords(b), Vector::from_coords(c), Vector::from_coords(point));
+    if p.same(a) || p.same(b) || p.same(c) {
+        return false;
+    }
+    // with vertices counterclockwise the point must lie at the left of every side, a clockwise check would also
+    // accept the antipodal triangle
+    let (b, c) = if a.dot(b.cross(c)) < 0_f64 { (c, b) } else { (b, c) };
+    [p.dot(a.cross(b)), p.dot(b.cross(c)), p.dot(c.cross(a))].iter().all(|s| *s > 0_f64)
+}
+
+/// checks if two great-circle segments cross, segments sharing an endpoint never cross
+pub(crate) fn segments_cross(a: LatLng, b: LatLng, c: LatLng, d: LatLng) -> bool {
+    let (a, b, c, d) = (Vector::from_coords(a), Vector::from_coords(b), Vector::from_coords(c), Vector::from_coords(d));
+    if a.same(c) || a.same(d) || b.same(c) || b.same(d) {
+        return false;
+    }
+
+    let ab = a.cross(b);
+    let cd = c.cross(d);
+    // endpoints of each segment must lie on opposite sides of the other segment's great circle
+    if ab.dot(c) * ab.dot(d) >= 0_f64 || cd.dot(a) * cd.dot(b) >= 0_f64 {
+        return false;
+    }
+
+    // great circles meet in two antipodal points, the right one must lie on both segments
+    let intersection = ab.cross(cd);
+    let intersection = if intersection.dot(a + b) < 0_f64 { -intersection } else { intersection };
+    intersection.dot(c + d) > 0_f64
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::coords::LatLng;
+
+    #[test]
+    fn distance() {
+        // one degree of latitude
+        let d = super::distance(LatLng::new(45_f64, 12_f64), LatLng::new(46_f64, 12_f64));
+        assert!((d - 111195_f64).abs() < 1_f64, "{d}");
+    }
+
+    #[test]
+    fn triangle() {
+        let (a, b, c) = (LatLng::new(0_f64, 0_f64), LatLng::new(0_f64, 1_f64), LatLng::new(1_f64, 0_f64));
+        let area = super::triangle_area(a, b, c);
+        // roughly half a square degree at the equator
+        assert!((area - 6.18e9).abs() < 1e7, "{area}");
+        assert!(super::triangle_contains(a, b, c, super::triangle_centroid(a, b, c)));
+        assert!(!super::triangle_contains(a, b, c, LatLng::new(1_f64, 1_f64)));
+        assert!(!super::triangle_contains(a, b, c, a));
+        // vertices order doesn't matter, antipodes are outside
+        assert!(super::triangle_contains(c, b, a, LatLng::new(0.33, 0.33)));
+        assert!(!super::triangle_contains(a, b, c, LatLng::new(-0.33, -179.67)));
+        assert!(!super::triangle_contains(c, b, a, LatLng::new(-0.33, -179.67)));
+    }
+
+    #[test]
+    fn segments_cross() {
+        let p = LatLng::new;
+        assert!(super::segments_cross(p(0_f64, 0_f64), p(1_f64, 1_f64), p(0_f64, 1_f64), p(1_f64, 0_f64)));
+        assert!(!super::segments_cross(p(0_f64, 0_f64), p(1_f64, 1_f64), p(0_f64, 0_f64), p(1_f64, 0_f64)));
+        assert!(!super::segments_cross(p(0_f64, 0_f64), p(1_f64, 1_f64), p(2_f64, 2_f64), p(3_f64, 1_f64)));
+    }
+}
@@ -1,4 +1,4 @@
-use std::{collections::HashMap, str::FromStr, sync::Arc};
+use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
 
 use reqwest::Method;
 use serde_json::json;
@@ -6,7 +6,7 @@ use smol_str::{SmolStr, ToSmolStr};
 use tokio::sync::Mutex;
 use tracing::error;
 
-use crate::{Error, call, entities, get_cookies, tile_key::TileKey};
+use crate::{Error, diff::Change, entities, get_cookies, tile_key::TileKey, world::World};
 
 pub(crate) struct Params<'a> {
     pub(crate) inner: &'a super::Intel<'a>,
@@ -54,14 +54,10 @@ impl Params<'_> {
                     Error::EntityRequest
                 })?;
 
-            call(&self.inner.client, req, &self.inner.cookie_store)
-                .await?
-                .json::<entities::IntelResponse>()
-                .await
-                .map_err(|e| {
-                    error!("error deserializing entities response: {}", e);
-                    Error::Deserialize
-                })
+            self.inner.call(req).await?.json::<entities::IntelResponse>().await.map_err(|e| {
+                error!("error deserializing entities response: {}", e);
+                Error::Deserialize
+            })
         };
 
         if let Ok(res) = inner_call.await {
@@ -102,6 +98,56 @@ impl Params<'_> {
         tracing::debug!("{free} free, {busy} busy, {done} done");
         (self, free + busy > 0)
     }
+
+    pub(crate) async fn reset(&self) {
+        for status in self.tiles.lock().await.values_mut() {
+            *status = TileState::Free;
+        }
+    }
+}
+
+// consecutive requests without progress after which a scan is abandoned
+const MAX_SCAN_FAILURES: usize = 5;
+
+pub(crate) struct Watch<'a> {
+    pub(crate) params: Arc<Params<'a>>,
+    pub(crate) throttle: Duration,
+    pub(crate) previous: Mutex<Option<World>>,
+}
+
+impl Watch<'_> {
+    /// full scan of the area, returns changes since the previous one
+    pub(crate) async fn scan(self: Arc<Self>) -> Option<Result<Vec<Change>, Error>> {
+        self.params.reset().await;
+        let mut world = World::default();
+        let mut failures = 0;
+        loop {
+            let (params, pending) = Arc::clone(&self.params).get_counts().await;
+            if !pending {
+                break;
+            }
+            match params.get_tiles().await {
+                Some(entities) if !entities.is_empty() => {
+                    failures = 0;
+                    world.extend(entities);
+                }
+                // neither the request nor any tile succeeded
+                _ => {
+                    failures += 1;
+                    if failures >= MAX_SCAN_FAILURES {
+                        error!("giving up area scan after {} failures", failures);
+                        return Some(Err(Error::Scan));
+                    }
+                }
+            }
+            tokio::time::sleep(self.throttle).await;
+        }
+
+        let mut previous = self.previous.lock().await;
+        let changes = previous.as_ref().map(|previous| previous.diff(&world));
+        *previous = Some(world);
+        changes.filter(|changes| !changes.is_empty()).map(Ok)
+    }
 }
 
 #[derive(Debug)]
@@ -0,0 +1,211 @@
+use std::fmt;
+
+use reqwest::Url;
+use scraper::{ElementRef, Html, Selector};
+use serde_json::{Map, value::Value};
+use smol_str::{SmolStr, ToSmolStr};
+
+const SNIPPET_LEN: usize = 512;
+const MAX_CANDIDATES: usize = 20;
+
+/// html form, ready to be submitted
+#[derive(Debug)]
+pub(crate) struct Form {
+    /// absolute form action
+    pub(crate) action: Url,
+    /// values to be submitted
+    pub(crate) fields: Value,
+    /// names of every input, even the ones that wouldn't be submitted
+    pub(crate) inputs: Vec<SmolStr>,
+}
+
+impl Form {
+    pub(crate) fn has_input(&self, name: &str) -> bool {
+        self.inputs.iter().any(|input| input == name)
+    }
+}
+
+/// describes which parsing step failed, with the candidates found on the page or a snippet of the offending markup
+#[derive(Debug)]
+pub(crate) struct Diagnostic {
+    step: &'static str,
+    snippet: String,
+}
+
+impl Diagnostic {
+    fn new(step: &'static str, html: &str) -> Self {
+        let end = html.char_indices().nth(SNIPPET_LEN).map_or(html.len(), |(pos, _)| pos);
+        Diagnostic { step, snippet: html[..end].to_owned() }
+    }
+
+    /// lists what the page offered instead, falls back to the page head when there's nothing
+    fn candidates(step: &'static str, page: &str, kind: &str, candidates: Vec<String>) -> Self {
+        if candidates.is_empty() {
+            return Diagnostic::new(step, page);
+        }
+        let mut snippet = format!("{} {kind} found", candidates.len());
+        for candidate in candidates.iter().take(MAX_CANDIDATES) {
+            snippet.push_str("\n  ");
+            snippet.push_str(candidate);
+        }
+        Diagnostic { step, snippet }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} failed, snippet:\n{}", self.step, self.snippet)
+    }
+}
+
+/// form selection criteria
+pub(crate) struct FormQuery<'a> {
+    /// any of these (attribute, value) couples identifies the form
+    pub(crate) attributes: &'a [(&'a str, &'a str)],
+    /// any of these input names identifies the form
+    pub(crate) inputs: &'a [&'a str],
+    /// action containing this fragment identifies the form
+    pub(crate) action_contains: Option<&'a str>,
+}
+
+impl FormQuery<'_> {
+    fn matches(&self, form: &ElementRef<'_>, input_names: &[SmolStr]) -> bool {
+        let element = form.value();
+        self.attributes.iter().any(|(name, value)| element.attr(name) == Some(*value))
+            || self.inputs.iter().any(|name| input_names.iter().any(|input| input == name))
+            || self.action_contains.is_some_and(|fragment| element.attr("action").is_some_and(|a| a.contains(fragment)))
+    }
+}
+
+fn selector(s: &str) -> Selector {
+    Selector::parse(s).expect("invalid selector")
+}
+
+/// locates a form inside the page and extracts its action and its fields
+pub(crate) fn find_form(
+    page: &str,
+    base_url: &str,
+    step: &'static str,
+    query: &FormQuery<'_>,
+) -> Result<Form, Diagnostic> {
+    let document = Html::parse_document(page);
+    let input_selector = selector("input[name]");
+
+    let (form, inputs) = document
+        .select(&selector("form"))
+        .map(|form| {
+            let inputs = form.select(&input_selector).collect::<Vec<_>>();
+            (form, inputs)
+        })
+        .find(|(form, inputs)| {
+            let names = inputs.iter().filter_map(|i| i.value().attr("name")).map(SmolStr::from).collect::<Vec<_>>();
+            query.matches(form, &names)
+        })
+        .ok_or_else(|| {
+            let forms = document.select(&selector("form")).map(|form| describe_form(&form, &input_selector)).collect();
+            Diagnostic::candidates(step, page, "forms", forms)
+        })?;
+
+    let base = Url::parse(base_url).map_err(|_| Diagnostic::new(step, base_url))?;
+    // a form without action submits to the page itself
+    let action = form.value().attr("action").unwrap_or_default();
+    let action = base.join(action).map_err(|_| Diagnostic::new(step, &form.html()))?;
+
+    let mut fields = Map::new();
+    let mut names = Vec::with_capacity(inputs.len());
+    for input in inputs {
+        let element = input.value();
+        let Some(name) = element.attr("name") else {
+            continue;
+        };
+        names.push(name.to_smolstr());
+        let unchecked = matches!(element.attr("type"), Some("radio" | "checkbox")) && element.attr("checked").is_none();
+        if !unchecked {
+            fields.insert(name.to_owned(), Value::from(element.attr("value").unwrap_or_default()));
+        }
+    }
+
+    Ok(Form { action, fields: Value::Object(fields), inputs: names })
+}
+
+// form opening tag attributes followed by its input names
+fn describe_form(form: &ElementRef<'_>, input_selector: &Selector) -> String {
+    let element = form.value();
+    let attributes = element.attrs().map(|(name, value)| format!("{name}='{value}'")).collect::<Vec<_>>().join(" ");
+    let inputs = form.select(input_selector).filter_map(|i| i.value().attr("name")).collect::<Vec<_>>().join(", ");
+    format!("<form {attributes}> inputs: {inputs}")
+}
+
+/// finds the first link whose target starts with given prefix
+pub(crate) fn find_link(page: &str, prefix: &str, step: &'static str) -> Result<SmolStr, Diagnostic> {
+    let document = Html::parse_document(page);
+    let links = document.select(&selector("a[href]")).filter_map(|a| a.value().attr("href")).collect::<Vec<_>>();
+    links.iter().find(|href| href.starts_with(prefix)).map(|href| SmolStr::from(*href)).ok_or_else(|| {
+        Diagnostic::candidates(step, page, "links", links.iter().map(|href| (*href).to_owned()).collect())
+    })
+}
+
+/// visible text of the page body, scripts and styles excluded
+pub(crate) fn body_text(page: &str) -> String {
+    let document = Html::parse_document(page);
+    document
+        .select(&selector("body"))
+        .flat_map(|body| body.descendants())
+        .filter_map(|node| {
+            let text = node.value().as_text()?;
+            let parent = node.parent()?.value().as_element()?;
+            (!matches!(parent.name(), "script" | "style" | "noscript")).then_some(&**text)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FormQuery;
+
+    #[test]
+    fn find_form() {
+        let page = r#"<html><body>
+            <form method='get' action='/search'><input name='q'></form>
+            <form action='/login/device-based/regular/login/?login_attempt=1&amp;lwv=110' method='post' id='login_form'>
+                <input value='lsd&#95;token' type='hidden' name='lsd'>
+                <input type=checkbox name=persistent>
+                <input type="text" name="email">
+                <input type="password" name="pass">
+            </form>
+        </body></html>"#;
+        let form = super::find_form(
+            page,
+            "https://www.facebook.com/?_fb_noscript=1",
+            "locating login form",
+            &FormQuery { attributes: &[("data-testid", "royal_login_form")], inputs: &["pass"], action_contains: None },
+        )
+        .unwrap();
+        assert_eq!(
+            form.action.as_str(),
+            "https://www.facebook.com/login/device-based/regular/login/?login_attempt=1&lwv=110"
+        );
+        assert_eq!(form.fields["lsd"], "lsd_token");
+        assert!(form.fields.get("persistent").is_none());
+        assert!(form.has_input("persistent"));
+    }
+
+    #[test]
+    fn diagnostic() {
+        let err =
+            super::find_link("<a href='https://example.com/'>nope</a>", "https://www.facebook.com/", "locating link")
+                .unwrap_err();
+        assert!(err.to_string().starts_with("locating link failed"));
+        assert!(err.to_string().contains("1 links found\n  https://example.com/"), "{err}");
+
+        let err = super::find_form(
+            "<form action='/search'><input name='q'></form>",
+            "https://www.facebook.com/",
+            "locating login form",
+            &FormQuery { attributes: &[], inputs: &["pass"], action_contains: None },
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("<form action='/search'> inputs: q"), "{err}");
+    }
+}
@@ -5,23 +5,35 @@
 //!
 //! Ingress Intel API interface in pure Rust
 
-use std::{borrow::Cow, collections::HashMap, convert::identity, iter::repeat, sync::Arc, time::Duration};
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    convert::identity,
+    iter::repeat,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use once_cell::sync::{Lazy, OnceCell};
-use percent_encoding::percent_decode_str;
 use regex::Regex;
 use reqwest::{Client, Method, Request, Response};
-use serde_json::{json, value::Value};
+use serde_json::json;
 use smol_str::{SmolStr, ToSmolStr};
 use tokio::sync::{Mutex, RwLock};
 use tokio_stream::{Stream, StreamExt};
-use tracing::error;
+use tracing::{error, warn};
 
+mod geo;
 mod get_entities_in_range;
+mod html;
+mod plexts_streams;
 mod tile_key;
 mod utils;
 use tile_key::TileKey;
 
+/// shared coordinate types
+pub mod coords;
+
 /// getEntities endpoint resource
 pub mod entities;
 
@@ -31,14 +43,41 @@ pub mod portal_details;
 /// getPlexts endpoint resources
 pub mod plexts;
 
+/// typed COMM events
+pub mod comm;
+
+/// redeemReward endpoint resources
+pub mod passcodes;
+
+/// getGameScore and getRegionScoreDetails endpoint resources
+pub mod scores;
+
+/// mission endpoints resources
+pub mod missions;
+
+/// session status resources
+pub mod session;
+
+/// Intel dashboard player resources
+pub mod player;
+
+/// login providers
+pub mod login;
+
+/// per-agent statistics
+pub mod agents;
+
+/// link and field planning
+pub mod planning;
+
+/// unified in-memory world model
+pub mod world;
+
+/// change detection between snapshots
+pub mod diff;
+
 const USER_AGENT: &str = "Mozilla/5.0 (X11; Ubuntu; Linux x86_64; rv:78.0) Gecko/20100101 Firefox/78.0";
 
-static INTEL_URLS: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<a[^>]+href="([^"]+)""#).unwrap());
-static FACEBOOK_LOGIN_FORM: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"<form[^>]+data-testid="royal_login_form"[^>]+action="([^"]+?)"[^>]+>([\s\S]+?)</form>"#).unwrap()
-});
-static INPUT_FIELDS: Lazy<Regex> = Lazy::new(|| Regex::new(r#"<input([^>]+)>"#).unwrap());
-static INPUT_ATTRIBUTES: Lazy<Regex> = Lazy::new(|| Regex::new(r#"([^\s="]+)="([^"]+)""#).unwrap());
 // static COOKIE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"([^=]+)=([^;]+)"#).unwrap());
 static API_VERSION: Lazy<Regex> = Lazy::new(|| Regex::new(r"/jsc/gen_dashboard_(\w+)\.js").unwrap());
 
@@ -72,6 +111,27 @@ pub enum Error {
     /// LoginForm error
     #[error("LoginForm")]
     LoginForm,
+    /// MissingGoogleUsername error
+    #[error("MissingGoogleUsername")]
+    MissingGoogleUsername,
+    /// MissingGooglePassword error
+    #[error("MissingGooglePassword")]
+    MissingGooglePassword,
+    /// GoogleUrl error
+    #[error("GoogleUrl")]
+    GoogleUrl,
+    /// GoogleRequest error
+    #[error("GoogleRequest")]
+    GoogleRequest,
+    /// TwoFactorRequired error, see `Intel::login_challenge`
+    #[error("TwoFactorRequired")]
+    TwoFactorRequired,
+    /// CheckpointRequired error, see `Intel::login_challenge`
+    #[error("CheckpointRequired")]
+    CheckpointRequired,
+    /// FacebookChallenge error
+    #[error("FacebookChallenge")]
+    FacebookChallenge,
     /// LoginFailed error
     #[error("LoginFailed")]
     LoginFailed,
@@ -87,6 +147,9 @@ pub enum Error {
     /// IntelApiVersion error
     #[error("IntelApiVersion")]
     IntelApiVersion,
+    /// Player error
+    #[error("Player")]
+    Player,
     /// EntityRequest error
     #[error("EntityRequest")]
     EntityRequest,
@@ -96,6 +159,30 @@ pub enum Error {
     /// PlextsRequest error
     #[error("PlextsRequest")]
     PlextsRequest,
+    /// SendPlextRequest error
+    #[error("SendPlextRequest")]
+    SendPlextRequest,
+    /// SendPlext error
+    #[error("SendPlext")]
+    SendPlext,
+    /// ReadOnlyTab error, messages can't be sent to the alerts tab
+    #[error("ReadOnlyTab")]
+    ReadOnlyTab,
+    /// RedeemRequest error
+    #[error("RedeemRequest")]
+    RedeemRequest,
+    /// ScoreRequest error
+    #[error("ScoreRequest")]
+    ScoreRequest,
+    /// MissionRequest error
+    #[error("MissionRequest")]
+    MissionRequest,
+    /// Scan error
+    #[error("Scan")]
+    Scan,
+    /// SessionRequest error
+    #[error("SessionRequest")]
+    SessionRequest,
     /// Deserialize error
     #[error("Deserialize")]
     Deserialize,
@@ -104,13 +191,9 @@ pub enum Error {
     Join,
 }
 
-async fn call(
-    client: &Client,
-    req: Request,
-    cookie_store: &RwLock<HashMap<SmolStr, SmolStr>>,
-) -> Result<Response, Error> {
+async fn send(client: &Client, req: Request) -> Result<Response, Error> {
     let url = req.url().to_smolstr();
-    let res = client
+    client
         .execute(req)
         .await
         .map_err(|e| {
@@ -121,7 +204,15 @@ async fn call(
         .map_err(|e| {
             error!("unsucessfull response from {}: {}", url, e);
             Error::Status
-        })?;
+        })
+}
+
+async fn call(
+    client: &Client,
+    req: Request,
+    cookie_store: &RwLock<HashMap<SmolStr, SmolStr>>,
+) -> Result<Response, Error> {
+    let res = send(client, req).await?;
 
     let mut lock = cookie_store.write().await;
     res.cookies().for_each(|c| {
@@ -136,95 +227,6 @@ async fn get_cookies(cookie_store: &RwLock<HashMap<SmolStr, SmolStr>>) -> String
     lock.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<String>>().join("; ")
 }
 
-async fn facebook_login(
-    client: &Client,
-    username: &str,
-    password: &str,
-    cookie_store: &RwLock<HashMap<SmolStr, SmolStr>>,
-) -> Result<(), Error> {
-    let req = client
-        .request(Method::GET, "https://www.facebook.com/?_fb_noscript=1")
-        // .header("Referer", "https://www.google.com/")
-        .header("User-Agent", USER_AGENT)
-        .build()
-        .map_err(|e| {
-            error!("error building first facebook request: {}", e);
-            Error::FirstFacebookRequest
-        })?;
-
-    let body = call(client, req, cookie_store).await?.text().await.map_err(|e| {
-        error!("error encoding response text: {}", e);
-        Error::FirstFacebookResponse
-    })?;
-
-    let captures = FACEBOOK_LOGIN_FORM.captures(&body).ok_or_else(|| {
-        error!("Facebook login form not found");
-        Error::LoginForm
-    })?;
-    let url = format!(
-        "https://www.facebook.com{}",
-        captures
-            .get(1)
-            .and_then(|m| percent_decode_str(&m.as_str().replace("&amp;", "&"))
-                .decode_utf8()
-                .ok()
-                .map(|s| s.to_smolstr()))
-            .ok_or_else(|| {
-                error!("Facebook login form URL not found\nbody: {}", body);
-                Error::LoginForm
-            })?
-    );
-    let form = captures.get(2).map(|m| m.as_str()).ok_or_else(|| {
-        error!("Facebook login form contents not found");
-        Error::LoginForm
-    })?;
-
-    let mut fields = Value::Null;
-    for m in INPUT_FIELDS.captures_iter(form) {
-        if let Some(input) = m.get(1) {
-            let (name, value) =
-                INPUT_ATTRIBUTES.captures_iter(input.as_str()).fold((None, None), |(mut name, mut value), im| {
-                    let key = im.get(1).map(|s| s.as_str());
-                    if key == Some("name") {
-                        name = im.get(2).map(|s| s.as_str());
-                    } else if key == Some("value") {
-                        value = im.get(2).map(|s| s.as_str());
-                    }
-                    (name, value)
-                });
-            if let Some(key) = name {
-                // if key != "_fb_noscript" && key != "sign_up" {
-                fields[key] = Value::from(value.unwrap_or_default());
-                // }
-            }
-        }
-    }
-
-    fields["email"] = Value::from(username);
-    fields["pass"] = Value::from(password);
-
-    let req = client
-        .request(Method::POST, &url)
-        // .header("Referer", "https://www.facebook.com/")
-        // .header("Origin", "https://www.facebook.com/")
-        .header("User-Agent", USER_AGENT)
-        .header("Cookie", get_cookies(cookie_store).await)
-        .form(&fields)
-        .build()
-        .map_err(|e| {
-            error!("error building second facebook request: {}", e);
-            Error::SecondFacebookRequest
-        })?;
-
-    let res = call(client, req, cookie_store).await?;
-    res.cookies().find(|c| c.name() == "c_user").ok_or_else(|| {
-        error!("Facebook login failed");
-        Error::LoginFailed
-    })?;
-
-    Ok(())
-}
-
 fn get_tile_keys_around(
     latitude: f64,
     longitude: f64,
@@ -250,39 +252,76 @@ fn get_tile_keys_around(
 
 /// Represents an Ingress Intel web client login
 pub struct Intel<'a> {
-    username: Option<Cow<'a, str>>,
-    password: Option<Cow<'a, str>>,
+    provider: Box<dyn login::LoginProvider + 'a>,
     client: Cow<'a, Client>,
     cookie_store: RwLock<HashMap<SmolStr, SmolStr>>,
     api_version: OnceCell<SmolStr>,
     csrftoken: OnceCell<SmolStr>,
+    player: OnceCell<player::Player>,
+    request_interval: Duration,
+    last_request: Mutex<Option<tokio::time::Instant>>,
 }
 
 impl<'a> Intel<'a> {
-    /// creates a new Ingress Intel web client login from existing Client
+    /// creates a new Ingress Intel web client login from existing Client, using Facebook login
     pub fn new(client: &'a Client, username: Option<Cow<'a, str>>, password: Option<Cow<'a, str>>) -> Self {
+        Self::with_provider(client, login::FacebookProvider::new(username, password))
+    }
+
+    /// creates a new Ingress Intel web client login, using Facebook login
+    pub fn build(username: Option<Cow<'a, str>>, password: Option<Cow<'a, str>>) -> Self {
+        Self::build_with_provider(login::FacebookProvider::new(username, password))
+    }
+
+    /// creates a new Ingress Intel web client login from existing Client, using given login provider
+    pub fn with_provider<P: login::LoginProvider + 'a>(client: &'a Client, provider: P) -> Self {
         Intel {
-            username,
-            password,
+            provider: Box::new(provider),
             client: Cow::Borrowed(client),
             cookie_store: Default::default(),
             api_version: OnceCell::new(),
             csrftoken: OnceCell::new(),
+            player: OnceCell::new(),
+            request_interval: Duration::ZERO,
+            last_request: Mutex::new(None),
         }
     }
 
-    /// creates a new Ingress Intel web client login
-    pub fn build(username: Option<Cow<'a, str>>, password: Option<Cow<'a, str>>) -> Self {
+    /// creates a new Ingress Intel web client login, using given login provider
+    pub fn build_with_provider<P: login::LoginProvider + 'a>(provider: P) -> Self {
         Intel {
-            username,
-            password,
+            provider: Box::new(provider),
             client: Cow::Owned(Client::new()),
             cookie_store: Default::default(),
             api_version: OnceCell::new(),
             csrftoken: OnceCell::new(),
+            player: OnceCell::new(),
+            request_interval: Duration::ZERO,
+            last_request: Mutex::new(None),
         }
     }
 
+    /// sets the minimum delay between any two Intel requests, shared by every call and stream of this client
+    pub fn with_request_interval(mut self, interval: Duration) -> Self {
+        self.request_interval = interval;
+        self
+    }
+
+    /// waits for the shared request interval to elapse
+    async fn pace(&self) {
+        let mut last = self.last_request.lock().await;
+        if let Some(last) = *last {
+            tokio::time::sleep_until(last + self.request_interval).await;
+        }
+        *last = Some(tokio::time::Instant::now());
+    }
+
+    /// performs an Intel request, paced by the shared request interval
+    async fn call(&self, req: Request) -> Result<Response, Error> {
+        self.pace().await;
+        call(&self.client, req, &self.cookie_store).await
+    }
+
     /// adds a cookie to the store
     pub async fn add_cookie<N, V>(&self, name: N, value: V)
     where
@@ -311,6 +350,11 @@ impl<'a> Intel<'a> {
         lock.get(cookie).is_some()
     }
 
+    /// challenge left unsolved by the last login attempt, e.g. the checkpoint URL after `Error::CheckpointRequired`
+    pub fn login_challenge(&self) -> Option<login::LoginChallenge> {
+        self.provider.login_challenge()
+    }
+
     /// performs login, if necessary
     pub async fn login(&self) -> Result<(), Error> {
         if self.api_version.get().is_some() {
@@ -319,42 +363,23 @@ impl<'a> Intel<'a> {
 
         // permits to add intel cookie without generating it everytime
         let url = if !self.cookie_exists("csrftoken").await {
-            // permits to add facebook cookie without generating it everytime
-            if !self.cookie_exists("c_user").await {
-                // login into facebook
-                facebook_login(
-                    &self.client,
-                    self.username.as_ref().ok_or_else(|| {
-                        error!("Missing facebok username");
-                        Error::MissingFacebookUsername
-                    })?,
-                    self.password.as_ref().ok_or_else(|| {
-                        error!("Missing facebook password");
-                        Error::MissingFacebookPassword
-                    })?,
-                    &self.cookie_store,
-                )
-                .await?;
+            // permits to add provider cookie without generating it everytime
+            let has_session = self.provider.has_session(&*self.cookie_store.read().await);
+            if !has_session {
+                // login into provider
+                self.provider.login(&self.client, &self.cookie_store).await?;
             }
 
-            // retrieve facebook login url
+            // retrieve provider login url
             let req = self.client.request(Method::GET, "https://intel.ingress.com/").build().map_err(|e| {
                 error!("error building first intel request: {}", e);
                 Error::FirstIntelRequest
             })?;
-            let intel = call(&self.client, req, &self.cookie_store).await?.text().await.map_err(|e| {
+            let intel = self.call(req).await?.text().await.map_err(|e| {
                 error!("error encoding first intel response: {}", e);
                 Error::FirstIntelRequest
             })?;
-            INTEL_URLS
-                .captures_iter(&intel)
-                .flat_map(|m| m.get(1).map(|s| s.as_str()))
-                .find(|s| s.starts_with("https://www.facebook.com/"))
-                .ok_or_else(|| {
-                    error!("Can't retrieve Intel's Facebook login URL");
-                    Error::FacebookUrl
-                })?
-                .to_smolstr()
+            self.provider.intel_login_url(&intel)?.unwrap_or_else(|| SmolStr::from("https://intel.ingress.com/"))
         } else {
             SmolStr::from("https://intel.ingress.com/")
         };
@@ -369,7 +394,7 @@ impl<'a> Intel<'a> {
                 error!("error building second intel request: {}", e);
                 Error::SecondIntelRequest
             })?;
-        let res = call(&self.client, req, &self.cookie_store).await?;
+        let res = self.call(req).await?;
         let csrftoken =
             res.cookies().find(|c| c.name() == "csrftoken").map(|c| c.value().to_smolstr()).ok_or_else(|| {
                 error!("Can't find csrftoken Cookie");
@@ -397,14 +422,53 @@ impl<'a> Intel<'a> {
             Error::IntelApiVersion
         })?;
 
+        if let Some(player) = player::Player::from_page(&intel) {
+            self.player.set(player).map_err(|_| {
+                error!("Can't set player");
+                Error::Player
+            })?;
+        } else {
+            warn!("Can't find Intel player data");
+        }
+
         Ok(())
     }
 
+    /// logged in agent profile, available after login
+    pub fn player(&self) -> Option<&player::Player> {
+        self.player.get()
+    }
+
+    /// checks if stored cookies still grant access to Intel, without performing any login nor updating stored cookies
+    pub async fn check_session(&self) -> Result<session::SessionStatus, Error> {
+        let req = self
+            .client
+            .request(Method::GET, "https://intel.ingress.com/")
+            .header("User-Agent", USER_AGENT)
+            .header("Cookie", get_cookies(&self.cookie_store).await)
+            .build()
+            .map_err(|e| {
+                error!("error building session request: {}", e);
+                Error::SessionRequest
+            })?;
+        // a check must not alter the session, response cookies are discarded
+        self.pace().await;
+        let intel = send(&self.client, req).await?.text().await.map_err(|e| {
+            error!("error encoding session response: {}", e);
+            Error::SessionRequest
+        })?;
+
+        Ok(session::SessionStatus::from_page(
+            &intel,
+            self.cookie_exists("sessionid").await,
+            self.api_version.get().map(SmolStr::as_str),
+        ))
+    }
+
     /// Retrieves entities informations for a given point
     pub async fn get_entities_around(
         &self,
-        latitude: f64,
-        longitude: f64,
+        center: coords::LatLng,
         zoom: Option<u8>,
         min_level: Option<u8>,
         max_level: Option<u8>,
@@ -418,7 +482,7 @@ impl<'a> Intel<'a> {
         })?;
 
         let body = json!({
-            "tileKeys": get_tile_keys_around(latitude, longitude, zoom, min_level, max_level, health),
+            "tileKeys": get_tile_keys_around(center.lat, center.lng, zoom, min_level, max_level, health),
             "v": self.api_version.get().ok_or_else(|| {
                 error!("missing API version");
                 Error::IntelApiVersion
@@ -439,7 +503,7 @@ impl<'a> Intel<'a> {
                 Error::EntityRequest
             })?;
 
-        call(&self.client, req, &self.cookie_store).await?.json().await.map_err(|e| {
+        self.call(req).await?.json().await.map_err(|e| {
             error!("error deserializing entities response: {}", e);
             Error::Deserialize
         })
@@ -449,8 +513,7 @@ impl<'a> Intel<'a> {
     #[allow(clippy::too_many_arguments)]
     pub async fn get_entities_in_range(
         &'a self,
-        from: (f64, f64),
-        to: (f64, f64),
+        bbox: coords::BoundingBox,
         zoom: Option<u8>,
         min_level: Option<u8>,
         max_level: Option<u8>,
@@ -468,7 +531,8 @@ impl<'a> Intel<'a> {
             Error::CsrfToken
         })?;
 
-        let tile_keys = TileKey::range(from, to, zoom, min_level, max_level, health);
+        let tile_keys =
+            TileKey::range(bbox.south_west.into(), bbox.north_east.into(), zoom, min_level, max_level, health);
 
         let params = get_entities_in_range::Params {
             inner: self,
@@ -492,6 +556,56 @@ impl<'a> Intel<'a> {
             .filter_map(identity))
     }
 
+    /// Continuously scans an area, yielding the changes found at every scan
+    ///
+    /// a new scan starts every `interval`, or as soon as the previous one ends if it takes longer,
+    /// `throttle` is the delay between single requests of this stream only, as in `get_entities_in_range`,
+    /// use `with_request_interval` to pace it together with every other request;
+    /// a scan failing repeatedly yields an error and is discarded, the next one is compared to the last complete one
+    #[allow(clippy::too_many_arguments)]
+    pub async fn watch_area(
+        &'a self,
+        bbox: coords::BoundingBox,
+        zoom: Option<u8>,
+        min_level: Option<u8>,
+        max_level: Option<u8>,
+        health: Option<u8>,
+        throttle: Duration,
+        interval: Duration,
+    ) -> Result<impl Stream<Item = Result<Vec<diff::Change>, Error>> + Send + Sync + 'a, Error> {
+        self.login().await?;
+
+        let api_version = self.api_version.get().map(ToOwned::to_owned).ok_or_else(|| {
+            error!("missing API version");
+            Error::IntelApiVersion
+        })?;
+        let csrftoken = self.csrftoken.get().map(ToOwned::to_owned).ok_or_else(|| {
+            error!("missing CSRFToken");
+            Error::CsrfToken
+        })?;
+
+        let tile_keys =
+            TileKey::range(bbox.south_west.into(), bbox.north_east.into(), zoom, min_level, max_level, health);
+
+        let watch = get_entities_in_range::Watch {
+            params: Arc::new(get_entities_in_range::Params {
+                inner: self,
+                tiles: Mutex::new(
+                    tile_keys.map(|tile| (tile, get_entities_in_range::TileState::Free)).collect::<HashMap<_, _>>(),
+                ),
+                api_version,
+                csrftoken,
+            }),
+            throttle,
+            previous: Mutex::new(None),
+        };
+
+        Ok(tokio_stream::iter(repeat(Arc::new(watch)))
+            .throttle(interval)
+            .then(get_entities_in_range::Watch::scan)
+            .filter_map(identity))
+    }
+
     /// Retrieves informations for a given portal
     pub async fn get_portal_details(&self, portal_id: &str) -> Result<portal_details::IntelResponse, Error> {
         self.login().await?;
@@ -520,7 +634,7 @@ impl<'a> Intel<'a> {
                 Error::PortalDetailsRequest
             })?;
 
-        call(&self.client, req, &self.cookie_store).await?.json().await.map_err(|e| {
+        self.call(req).await?.json().await.map_err(|e| {
             error!("error deserializing portal details response: {}", e);
             Error::Deserialize
         })
@@ -529,8 +643,7 @@ impl<'a> Intel<'a> {
     /// Retrieves COMM contents
     pub async fn get_plexts(
         &self,
-        from: [u64; 2],
-        to: [u64; 2],
+        bbox: coords::BoundingBox,
         tab: plexts::Tab,
         min_timestamp_ms: Option<i64>,
         max_timestamp_ms: Option<i64>,
@@ -543,10 +656,10 @@ impl<'a> Intel<'a> {
         })?;
 
         let body = json!({
-            "minLatE6": from[0],
-            "minLngE6": from[1],
-            "maxLatE6": to[0],
-            "maxLngE6": to[1],
+            "minLatE6": bbox.south_west.lat_e6(),
+            "minLngE6": bbox.south_west.lng_e6(),
+            "maxLatE6": bbox.north_east.lat_e6(),
+            "maxLngE6": bbox.north_east.lng_e6(),
             "minTimestampMs": min_timestamp_ms.unwrap_or(-1),
             "maxTimestampMs": max_timestamp_ms.unwrap_or(-1),
             "tab": tab,
@@ -567,11 +680,366 @@ impl<'a> Intel<'a> {
                 Error::PlextsRequest
             })?;
 
-        call(&self.client, req, &self.cookie_store).await?.json().await.map_err(|e| {
+        self.call(req).await?.json().await.map_err(|e| {
             error!("error deserializing portal details response: {}", e);
             Error::Deserialize
         })
     }
+
+    /// Sends a COMM message from the given position to the all or faction tab, alerts tab gives `Error::ReadOnlyTab`
+    ///
+    /// returns the sent message as read back from COMM, looking for the newest one with the same sender and text
+    /// in the last minute around the position, so an identical message sent shortly before may be returned instead;
+    /// `None` when it doesn't show up yet, or when the agent nickname is unknown, e.g. without `player` data after a
+    /// cookies-only login
+    pub async fn send_plext(
+        &self,
+        message: impl Into<plexts::Message>,
+        position: coords::LatLng,
+        tab: plexts::Tab,
+    ) -> Result<Option<plexts::IntelPlext>, Error> {
+        if tab == plexts::Tab::Alerts {
+            error!("can't send messages to the alerts tab");
+            return Err(Error::ReadOnlyTab);
+        }
+
+        self.login().await?;
+
+        let message = message.into();
+
+        let csrftoken = self.csrftoken.get().ok_or_else(|| {
+            error!("missing CSRFToken");
+            Error::CsrfToken
+        })?;
+
+        // some slack for clock differences
+        let sent_at =
+            SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or_default() - 60000;
+
+        let body = json!({
+            "message": message.as_str(),
+            "latE6": position.lat_e6(),
+            "lngE6": position.lng_e6(),
+            "tab": tab,
+            "v": self.api_version.get().unwrap(),
+        });
+
+        let req = self
+            .client
+            .request(Method::POST, "https://intel.ingress.com/r/sendPlext")
+            .header("Referer", "https://intel.ingress.com/")
+            .header("Origin", "https://intel.ingress.com/")
+            .header("Cookie", get_cookies(&self.cookie_store).await)
+            .header("X-CSRFToken", csrftoken.as_str())
+            .json(&body)
+            .build()
+            .map_err(|e| {
+                error!("error building send plext request: {}", e);
+                Error::SendPlextRequest
+            })?;
+
+        let res = self.call(req).await?.json::<serde_json::Value>().await.map_err(|e| {
+            error!("error deserializing send plext response: {}", e);
+            Error::Deserialize
+        })?;
+        if res.get("result").and_then(serde_json::Value::as_str) != Some("success") {
+            error!("send plext failed: {}", res);
+            return Err(Error::SendPlext);
+        }
+
+        let Some(nickname) = self.player().map(|p| p.nickname.clone()) else {
+            return Ok(None);
+        };
+        let area = coords::BoundingBox::new(
+            (position.lat - 0.001, position.lng - 0.001),
+            (position.lat + 0.001, position.lng + 0.001),
+        );
+        let sent = self.get_plexts(area, tab, Some(sent_at), None).await?;
+        Ok(sent.result.into_iter().find(|(_, _, wrapper)| match wrapper.plext.event() {
+            comm::CommEvent::Chat { sender, text, .. } | comm::CommEvent::SecureMessage { sender, text, .. } => {
+                sender.name == nickname && text == message.as_str().trim()
+            }
+            _ => false,
+        }))
+    }
+
+    /// Redeems a passcode
+    ///
+    /// the outer error is about the request itself, the inner one is the reason Intel refused the passcode
+    pub async fn redeem_passcode(
+        &self,
+        passcode: &str,
+    ) -> Result<Result<passcodes::Reward, passcodes::RedeemError>, Error> {
+        self.login().await?;
+
+        let csrftoken = self.csrftoken.get().ok_or_else(|| {
+            error!("missing CSRFToken");
+            Error::CsrfToken
+        })?;
+
+        let body = json!({
+            "passcode": passcode,
+            "v": self.api_version.get().unwrap(),
+        });
+
+        let req = self
+            .client
+            .request(Method::POST, "https://intel.ingress.com/r/redeemReward")
+            .header("Referer", "https://intel.ingress.com/")
+            .header("Origin", "https://intel.ingress.com/")
+            .header("Cookie", get_cookies(&self.cookie_store).await)
+            .header("X-CSRFToken", csrftoken.as_str())
+            .json(&body)
+            .build()
+            .map_err(|e| {
+                error!("error building redeem request: {}", e);
+                Error::RedeemRequest
+            })?;
+
+        self.call(req)
+            .await?
+            .json::<passcodes::IntelResponse>()
+            .await
+            .map(passcodes::IntelResponse::into_result)
+            .map_err(|e| {
+                error!("error deserializing redeem response: {}", e);
+                Error::Deserialize
+            })
+    }
+
+    /// Retrieves global MU per faction
+    pub async fn get_game_score(&self) -> Result<scores::GameScoreResponse, Error> {
+        self.login().await?;
+
+        let csrftoken = self.csrftoken.get().ok_or_else(|| {
+            error!("missing CSRFToken");
+            Error::CsrfToken
+        })?;
+
+        let body = json!({
+            "v": self.api_version.get().unwrap(),
+        });
+
+        let req = self
+            .client
+            .request(Method::POST, "https://intel.ingress.com/r/getGameScore")
+            .header("Referer", "https://intel.ingress.com/")
+            .header("Origin", "https://intel.ingress.com/")
+            .header("Cookie", get_cookies(&self.cookie_store).await)
+            .header("X-CSRFToken", csrftoken.as_str())
+            .json(&body)
+            .build()
+            .map_err(|e| {
+                error!("error building game score request: {}", e);
+                Error::ScoreRequest
+            })?;
+
+        self.call(req).await?.json().await.map_err(|e| {
+            error!("error deserializing game score response: {}", e);
+            Error::Deserialize
+        })
+    }
+
+    /// Retrieves score details of the cell containing the given point
+    pub async fn get_region_score_details(
+        &self,
+        position: coords::LatLng,
+    ) -> Result<scores::RegionScoreResponse, Error> {
+        self.login().await?;
+
+        let csrftoken = self.csrftoken.get().ok_or_else(|| {
+            error!("missing CSRFToken");
+            Error::CsrfToken
+        })?;
+
+        let body = json!({
+            "latE6": position.lat_e6(),
+            "lngE6": position.lng_e6(),
+            "v": self.api_version.get().unwrap(),
+        });
+
+        let req = self
+            .client
+            .request(Method::POST, "https://intel.ingress.com/r/getRegionScoreDetails")
+            .header("Referer", "https://intel.ingress.com/")
+            .header("Origin", "https://intel.ingress.com/")
+            .header("Cookie", get_cookies(&self.cookie_store).await)
+            .header("X-CSRFToken", csrftoken.as_str())
+            .json(&body)
+            .build()
+            .map_err(|e| {
+                error!("error building region score request: {}", e);
+                Error::ScoreRequest
+            })?;
+
+        self.call(req).await?.json().await.map_err(|e| {
+            error!("error deserializing region score response: {}", e);
+            Error::Deserialize
+        })
+    }
+
+    /// Retrieves top missions starting inside an area
+    pub async fn get_top_missions_in_bounds(
+        &self,
+        bbox: coords::BoundingBox,
+    ) -> Result<missions::MissionsResponse, Error> {
+        self.login().await?;
+
+        let csrftoken = self.csrftoken.get().ok_or_else(|| {
+            error!("missing CSRFToken");
+            Error::CsrfToken
+        })?;
+
+        let body = json!({
+            "northE6": bbox.north_east.lat_e6(),
+            "eastE6": bbox.north_east.lng_e6(),
+            "southE6": bbox.south_west.lat_e6(),
+            "westE6": bbox.south_west.lng_e6(),
+            "v": self.api_version.get().unwrap(),
+        });
+
+        let req = self
+            .client
+            .request(Method::POST, "https://intel.ingress.com/r/getTopMissionsInBounds")
+            .header("Referer", "https://intel.ingress.com/")
+            .header("Origin", "https://intel.ingress.com/")
+            .header("Cookie", get_cookies(&self.cookie_store).await)
+            .header("X-CSRFToken", csrftoken.as_str())
+            .json(&body)
+            .build()
+            .map_err(|e| {
+                error!("error building top missions request: {}", e);
+                Error::MissionRequest
+            })?;
+
+        self.call(req).await?.json().await.map_err(|e| {
+            error!("error deserializing top missions response: {}", e);
+            Error::Deserialize
+        })
+    }
+
+    /// Retrieves top missions starting from a portal
+    pub async fn get_top_missions_for_portal(&self, portal_id: &str) -> Result<missions::MissionsResponse, Error> {
+        self.login().await?;
+
+        let csrftoken = self.csrftoken.get().ok_or_else(|| {
+            error!("missing CSRFToken");
+            Error::CsrfToken
+        })?;
+
+        let body = json!({
+            "guid": portal_id,
+            "v": self.api_version.get().unwrap(),
+        });
+
+        let req = self
+            .client
+            .request(Method::POST, "https://intel.ingress.com/r/getTopMissionsForPortal")
+            .header("Referer", "https://intel.ingress.com/")
+            .header("Origin", "https://intel.ingress.com/")
+            .header("Cookie", get_cookies(&self.cookie_store).await)
+            .header("X-CSRFToken", csrftoken.as_str())
+            .json(&body)
+            .build()
+            .map_err(|e| {
+                error!("error building portal missions request: {}", e);
+                Error::MissionRequest
+            })?;
+
+        self.call(req).await?.json().await.map_err(|e| {
+            error!("error deserializing portal missions response: {}", e);
+            Error::Deserialize
+        })
+    }
+
+    /// Retrieves mission details
+    pub async fn get_mission_details(&self, mission_id: &str) -> Result<missions::MissionDetailsResponse, Error> {
+        self.login().await?;
+
+        let csrftoken = self.csrftoken.get().ok_or_else(|| {
+            error!("missing CSRFToken");
+            Error::CsrfToken
+        })?;
+
+        let body = json!({
+            "guid": mission_id,
+            "v": self.api_version.get().unwrap(),
+        });
+
+        let req = self
+            .client
+            .request(Method::POST, "https://intel.ingress.com/r/getMissionDetails")
+            .header("Referer", "https://intel.ingress.com/")
+            .header("Origin", "https://intel.ingress.com/")
+            .header("Cookie", get_cookies(&self.cookie_store).await)
+            .header("X-CSRFToken", csrftoken.as_str())
+            .json(&body)
+            .build()
+            .map_err(|e| {
+                error!("error building mission details request: {}", e);
+                Error::MissionRequest
+            })?;
+
+        self.call(req).await?.json().await.map_err(|e| {
+            error!("error deserializing mission details response: {}", e);
+            Error::Deserialize
+        })
+    }
+
+    /// Pages backwards through COMM history, from newest to `since_ms`
+    ///
+    /// messages are yielded newest first and deduplicated across pages, `throttle` is the delay between requests;
+    /// failures are retried, the stream ends with the error after too many consecutive ones;
+    /// Intel pages by timestamp, so when more than a page of messages share the same millisecond the exceeding
+    /// ones can't be reached and are skipped with a warning
+    pub async fn backfill_plexts(
+        &'a self,
+        bbox: coords::BoundingBox,
+        tab: plexts::Tab,
+        since_ms: i64,
+        throttle: Duration,
+    ) -> Result<impl Stream<Item = Result<Vec<plexts::IntelPlext>, Error>> + Send + 'a, Error> {
+        self.login().await?;
+
+        let backfill =
+            plexts_streams::Backfill { inner: self, bbox, tab, since: since_ms, state: Mutex::new(Default::default()) };
+
+        Ok(tokio_stream::iter(repeat(Arc::new(backfill)))
+            .throttle(throttle)
+            .then(plexts_streams::Backfill::next_page)
+            .take_while(Option::is_some)
+            .filter_map(identity)
+            .filter(|page| !page.as_ref().is_ok_and(Vec::is_empty)))
+    }
+
+    /// Polls COMM for new messages, yielding them oldest first
+    ///
+    /// messages newer than `since_ms` are yielded, without it the stream starts from the next new message;
+    /// polling speeds up to a quarter of `poll_interval` on busy areas and slows down up to four times it on quiet
+    /// ones or on failures, which are retried, the stream ends with the error after too many consecutive ones
+    pub async fn tail_plexts(
+        &'a self,
+        bbox: coords::BoundingBox,
+        tab: plexts::Tab,
+        poll_interval: Duration,
+        since_ms: Option<i64>,
+    ) -> Result<impl Stream<Item = Result<Vec<plexts::IntelPlext>, Error>> + Send + 'a, Error> {
+        self.login().await?;
+
+        let tail = plexts_streams::Tail {
+            inner: self,
+            bbox,
+            tab,
+            poll_interval,
+            state: Mutex::new(plexts_streams::TailState::new(since_ms, poll_interval)),
+        };
+
+        Ok(tokio_stream::iter(repeat(Arc::new(tail)))
+            .then(plexts_streams::Tail::next_messages)
+            .take_while(Option::is_some)
+            .filter_map(identity)
+            .filter(|messages| !messages.as_ref().is_ok_and(Vec::is_empty)))
+    }
 }
 
 #[cfg(test)]
@@ -599,6 +1067,23 @@ mod tests {
         intel
     }
 
+    #[tokio::test]
+    async fn request_interval() {
+        let intel = super::Intel::build(None, None).with_request_interval(Duration::from_millis(50));
+        let start = tokio::time::Instant::now();
+        for _ in 0..3 {
+            intel.pace().await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(100));
+    }
+
+    fn bbox() -> super::coords::BoundingBox {
+        super::coords::BoundingBox::new(
+            (env::var("LATITUDE_FROM").unwrap().parse().unwrap(), env::var("LONGITUDE_FROM").unwrap().parse().unwrap()),
+            (env::var("LATITUDE_TO").unwrap().parse().unwrap(), env::var("LONGITUDE_TO").unwrap().parse().unwrap()),
+        )
+    }
+
     #[test_with::env(LATITUDE, LONGITUDE)]
     #[tokio::test]
     async fn get_entities_around() {
@@ -607,8 +1092,10 @@ mod tests {
             "get_entities_around {:#?}",
             intel
                 .get_entities_around(
-                    env::var("LATITUDE").unwrap().parse().unwrap(),
-                    env::var("LONGITUDE").unwrap().parse().unwrap(),
+                    super::coords::LatLng::new(
+                        env::var("LATITUDE").unwrap().parse().unwrap(),
+                        env::var("LONGITUDE").unwrap().parse().unwrap()
+                    ),
                     env::var("ZOOM").ok().as_deref().map(str::parse).transpose().unwrap(),
                     env::var("MIN_LEVEL").ok().as_deref().map(str::parse).transpose().unwrap(),
                     None,
@@ -627,14 +1114,7 @@ mod tests {
             "get_entities_in_range {:#?}",
             intel
                 .get_entities_in_range(
-                    (
-                        env::var("LATITUDE_FROM").unwrap().parse().unwrap(),
-                        env::var("LONGITUDE_FROM").unwrap().parse().unwrap()
-                    ),
-                    (
-                        env::var("LATITUDE_TO").unwrap().parse().unwrap(),
-                        env::var("LONGITUDE_TO").unwrap().parse().unwrap()
-                    ),
+                    bbox(),
                     env::var("ZOOM").ok().as_deref().map(str::parse).transpose().unwrap(),
                     env::var("MIN_LEVEL").ok().as_deref().map(str::parse).transpose().unwrap(),
                     None,
@@ -648,6 +1128,139 @@ mod tests {
         );
     }
 
+    #[test_with::env(LATITUDE_FROM, LONGITUDE_FROM, LATITUDE_TO, LONGITUDE_TO, WATCH_INTERVAL)]
+    #[tokio::test]
+    async fn watch_area() {
+        let intel = login().await;
+        let stream = intel
+            .watch_area(
+                bbox(),
+                env::var("ZOOM").ok().as_deref().map(str::parse).transpose().unwrap(),
+                env::var("MIN_LEVEL").ok().as_deref().map(str::parse).transpose().unwrap(),
+                None,
+                None,
+                Duration::from_millis(1500),
+                Duration::from_secs(env::var("WATCH_INTERVAL").unwrap().parse().unwrap()),
+            )
+            .await
+            .unwrap();
+        tokio::pin!(stream);
+        info!("watch_area {:#?}", stream.next().await);
+    }
+
+    #[test_with::env(LATITUDE_FROM, LONGITUDE_FROM, LATITUDE_TO, LONGITUDE_TO, BACKFILL_HOURS)]
+    #[tokio::test]
+    async fn backfill_plexts() {
+        let intel = login().await;
+        let since = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap()
+            - Duration::from_secs(env::var("BACKFILL_HOURS").unwrap().parse::<u64>().unwrap() * 3600);
+        let pages = intel
+            .backfill_plexts(bbox(), crate::plexts::Tab::All, since.as_millis() as i64, Duration::from_millis(1500))
+            .await
+            .unwrap()
+            .collect::<Vec<_>>()
+            .await;
+        info!("backfill_plexts {} messages", pages.into_iter().map(|page| page.unwrap().len()).sum::<usize>());
+    }
+
+    #[test_with::env(LATITUDE_FROM, LONGITUDE_FROM, LATITUDE_TO, LONGITUDE_TO, TAIL_HOURS)]
+    #[tokio::test]
+    async fn tail_plexts() {
+        let intel = login().await;
+        let since = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap()
+            - Duration::from_secs(env::var("TAIL_HOURS").unwrap().parse::<u64>().unwrap() * 3600);
+        let stream = intel
+            .tail_plexts(bbox(), crate::plexts::Tab::All, Duration::from_secs(30), Some(since.as_millis() as i64))
+            .await
+            .unwrap();
+        tokio::pin!(stream);
+        info!("tail_plexts {:#?}", stream.next().await);
+    }
+
+    #[test_with::env(LATITUDE, LONGITUDE, SEND_PLEXT)]
+    #[tokio::test]
+    async fn send_plext() {
+        let intel = login().await;
+        info!(
+            "send_plext {:#?}",
+            intel
+                .send_plext(
+                    env::var("SEND_PLEXT").unwrap().as_str(),
+                    super::coords::LatLng::new(
+                        env::var("LATITUDE").unwrap().parse().unwrap(),
+                        env::var("LONGITUDE").unwrap().parse().unwrap()
+                    ),
+                    super::plexts::Tab::Faction,
+                )
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn send_plext_alerts() {
+        let intel = super::Intel::build(None, None);
+        let res = intel.send_plext("hello", super::coords::LatLng::new(45.0, 12.0), super::plexts::Tab::Alerts).await;
+        assert!(matches!(res, Err(super::Error::ReadOnlyTab)));
+    }
+
+    #[test_with::env(PASSCODE)]
+    #[tokio::test]
+    async fn redeem_passcode() {
+        let intel = login().await;
+        info!("redeem_passcode {:#?}", intel.redeem_passcode(env::var("PASSCODE").unwrap().as_str()).await.unwrap());
+    }
+
+    #[test_with::env(COOKIES)]
+    #[tokio::test]
+    async fn get_game_score() {
+        let intel = login().await;
+        info!("get_game_score {:#?}", intel.get_game_score().await.unwrap());
+    }
+
+    #[test_with::env(LATITUDE, LONGITUDE)]
+    #[tokio::test]
+    async fn get_region_score_details() {
+        let intel = login().await;
+        info!(
+            "get_region_score_details {:#?}",
+            intel
+                .get_region_score_details(super::coords::LatLng::new(
+                    env::var("LATITUDE").unwrap().parse().unwrap(),
+                    env::var("LONGITUDE").unwrap().parse().unwrap()
+                ))
+                .await
+                .unwrap()
+        );
+    }
+
+    #[test_with::env(LATITUDE_FROM, LONGITUDE_FROM, LATITUDE_TO, LONGITUDE_TO)]
+    #[tokio::test]
+    async fn get_top_missions_in_bounds() {
+        let intel = login().await;
+        info!("get_top_missions_in_bounds {:#?}", intel.get_top_missions_in_bounds(bbox()).await.unwrap());
+    }
+
+    #[test_with::env(PORTAL_ID)]
+    #[tokio::test]
+    async fn get_top_missions_for_portal() {
+        let intel = login().await;
+        info!(
+            "get_top_missions_for_portal {:#?}",
+            intel.get_top_missions_for_portal(env::var("PORTAL_ID").unwrap().as_str()).await.unwrap()
+        );
+    }
+
+    #[test_with::env(MISSION_ID)]
+    #[tokio::test]
+    async fn get_mission_details() {
+        let intel = login().await;
+        info!(
+            "get_mission_details {:#?}",
+            intel.get_mission_details(env::var("MISSION_ID").unwrap().as_str()).await.unwrap()
+        );
+    }
+
     #[test_with::env(PORTAL_ID)]
     #[tokio::test]
     async fn get_portal_details() {
@@ -657,4 +1270,19 @@ mod tests {
             intel.get_portal_details(env::var("PORTAL_ID").unwrap().as_str()).await.unwrap()
         );
     }
+
+    #[test_with::env(COOKIES)]
+    #[tokio::test]
+    async fn check_session() {
+        let intel = login().await;
+        info!("check_session {:?}", intel.check_session().await.unwrap());
+    }
+
+    #[test_with::env(COOKIES)]
+    #[tokio::test]
+    async fn player() {
+        let intel = login().await;
+        intel.login().await.unwrap();
+        info!("player {:?}", intel.player());
+    }
 }
@@ -0,0 +1,573 @@
+use std::{borrow::Cow, collections::HashMap, future::Future, pin::Pin, sync::Mutex};
+
+use reqwest::{Client, Method, Response};
+use serde_json::value::Value;
+use smol_str::{SmolStr, ToSmolStr};
+use tokio::sync::RwLock;
+use tracing::error;
+
+use crate::{
+    Error, USER_AGENT, call, get_cookies,
+    html::{self, FormQuery},
+};
+
+const MAX_CHALLENGE_STEPS: usize = 5;
+
+const FACEBOOK_LOGIN_FORM: FormQuery<'static> = FormQuery {
+    attributes: &[("data-testid", "royal_login_form"), ("id", "login_form")],
+    inputs: &["pass"],
+    action_contains: None,
+};
+const GOOGLE_LOGIN_FORM: FormQuery<'static> =
+    FormQuery { attributes: &[("id", "gaia_loginform")], inputs: &["Email", "Passwd"], action_contains: None };
+const CHECKPOINT_FORM: FormQuery<'static> = FormQuery {
+    attributes: &[],
+    inputs: &["approvals_code", "name_action_selected"],
+    action_contains: Some("checkpoint"),
+};
+
+/// shared cookie jar, keyed by cookie name
+pub type CookieStore = RwLock<HashMap<SmolStr, SmolStr>>;
+
+/// boxed future returned by `LoginProvider::login`
+pub type LoginFuture<'a> = Pin<Box<dyn Future<Output = Result<(), Error>> + Send + 'a>>;
+
+/// boxed future returned by a `ChallengeHandler`
+pub type ChallengeFuture = Pin<Box<dyn Future<Output = Option<SmolStr>> + Send>>;
+
+/// callback invoked when login requires user interaction
+pub type ChallengeHandler<'a> = Box<dyn Fn(LoginChallenge) -> ChallengeFuture + Send + Sync + 'a>;
+
+/// Intermediate step requested by the identity provider during login
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum LoginChallenge {
+    /// a two-factor code is needed, handler must return it
+    TwoFactor {
+        /// challenge page URL
+        url: SmolStr,
+    },
+    /// a security checkpoint must be cleared elsewhere, handler must return `Some` once done to continue the flow,
+    /// the returned value itself is ignored
+    Checkpoint {
+        /// checkpoint page URL
+        url: SmolStr,
+    },
+}
+
+/// Identity provider used to obtain an Intel session
+pub trait LoginProvider: Send + Sync {
+    /// checks if the store already contains a provider session
+    fn has_session(&self, cookies: &HashMap<SmolStr, SmolStr>) -> bool;
+
+    /// performs provider login, storing obtained cookies into the store
+    fn login<'a>(&'a self, client: &'a Client, cookie_store: &'a CookieStore) -> LoginFuture<'a>;
+
+    /// extracts provider's login URL from Intel's login page, `None` means going straight to Intel
+    fn intel_login_url(&self, intel: &str) -> Result<Option<SmolStr>, Error>;
+
+    /// challenge left unsolved by the last login attempt, if any
+    fn login_challenge(&self) -> Option<LoginChallenge> {
+        None
+    }
+}
+
+/// Facebook login
+pub struct FacebookProvider<'a> {
+    username: Option<Cow<'a, str>>,
+    password: Option<Cow<'a, str>>,
+    challenge_handler: Option<ChallengeHandler<'a>>,
+    last_challenge: Mutex<Option<LoginChallenge>>,
+    base_url: Cow<'a, str>,
+}
+
+impl<'a> FacebookProvider<'a> {
+    /// creates a new Facebook login provider
+    pub fn new(username: Option<Cow<'a, str>>, password: Option<Cow<'a, str>>) -> Self {
+        FacebookProvider {
+            username,
+            password,
+            challenge_handler: None,
+            last_challenge: Mutex::new(None),
+            base_url: Cow::Borrowed("https://www.facebook.com"),
+        }
+    }
+
+    /// sets the callback used to solve two-factor and checkpoint challenges
+    pub fn with_challenge_handler<F, Fut>(mut self, handler: F) -> Self
+    where
+        F: Fn(LoginChallenge) -> Fut + Send + Sync + 'a,
+        Fut: Future<Output = Option<SmolStr>> + Send + 'static,
+    {
+        self.challenge_handler = Some(Box::new(move |challenge| Box::pin(handler(challenge))));
+        self
+    }
+
+    async fn solve_challenges(&self, client: &Client, cookie_store: &CookieStore, res: Response) -> Result<(), Error> {
+        let mut url = res.url().to_smolstr();
+        let mut body = res.text().await.map_err(|e| {
+            error!("error encoding response text: {}", e);
+            Error::FacebookChallenge
+        })?;
+
+        for _ in 0..MAX_CHALLENGE_STEPS {
+            if self.has_session(&*cookie_store.read().await) {
+                return Ok(());
+            }
+
+            let form = html::find_form(&body, &url, "locating Facebook checkpoint form", &CHECKPOINT_FORM);
+            let mut fields = form.as_ref().map(|f| f.fields.clone()).unwrap_or_default();
+            if form.as_ref().is_ok_and(|f| f.has_input("approvals_code")) {
+                let challenge = LoginChallenge::TwoFactor { url: url.clone() };
+                let code = self.ask(challenge).await.ok_or_else(|| {
+                    error!("Facebook two-factor code required at {}", url);
+                    Error::TwoFactorRequired
+                })?;
+                fields["approvals_code"] = Value::from(code.as_str());
+            } else if form.as_ref().is_ok_and(|f| f.has_input("name_action_selected")) {
+                // "remember browser" step, no user interaction needed
+                fields["name_action_selected"] = Value::from("save_device");
+            } else if url.contains("/checkpoint") || form.is_ok() {
+                let challenge = LoginChallenge::Checkpoint { url: url.clone() };
+                self.ask(challenge).await.ok_or_else(|| {
+                    error!("Facebook checkpoint required at {}", url);
+                    Error::CheckpointRequired
+                })?;
+            } else {
+                if let Err(diagnostic) = form {
+                    error!("{}", diagnostic);
+                }
+                error!("Facebook login failed");
+                return Err(Error::LoginFailed);
+            }
+
+            let req = match &form {
+                Ok(form) => client
+                    .request(Method::POST, form.action.clone())
+                    .header("User-Agent", USER_AGENT)
+                    .header("Cookie", get_cookies(cookie_store).await)
+                    .form(&fields),
+                // checkpoint has been cleared elsewhere, reload the page
+                Err(_) => client
+                    .request(Method::GET, url.as_str())
+                    .header("User-Agent", USER_AGENT)
+                    .header("Cookie", get_cookies(cookie_store).await),
+            }
+            .build()
+            .map_err(|e| {
+                error!("error building facebook challenge request: {}", e);
+                Error::FacebookChallenge
+            })?;
+
+            let res = call(client, req, cookie_store).await?;
+            url = res.url().to_smolstr();
+            body = res.text().await.map_err(|e| {
+                error!("error encoding response text: {}", e);
+                Error::FacebookChallenge
+            })?;
+        }
+
+        if self.has_session(&*cookie_store.read().await) {
+            Ok(())
+        } else {
+            error!("Facebook login failed after {} challenge steps", MAX_CHALLENGE_STEPS);
+            Err(Error::LoginFailed)
+        }
+    }
+
+    async fn ask(&self, challenge: LoginChallenge) -> Option<SmolStr> {
+        let answer = match &self.challenge_handler {
+            Some(handler) => handler(challenge.clone()).await,
+            None => None,
+        };
+        if answer.is_none() {
+            // keep it around so that the caller can solve it by hand
+            *self.last_challenge.lock().unwrap() = Some(challenge);
+        }
+        answer
+    }
+
+    async fn facebook_login(&self, client: &Client, cookie_store: &CookieStore) -> Result<(), Error> {
+        self.last_challenge.lock().unwrap().take();
+
+        let username = self.username.as_ref().ok_or_else(|| {
+            error!("Missing facebok username");
+            Error::MissingFacebookUsername
+        })?;
+        let password = self.password.as_ref().ok_or_else(|| {
+            error!("Missing facebook password");
+            Error::MissingFacebookPassword
+        })?;
+
+        let req = client
+            .request(Method::GET, format!("{}/?_fb_noscript=1", self.base_url))
+            // .header("Referer", "https://www.google.com/")
+            .header("User-Agent", USER_AGENT)
+            .build()
+            .map_err(|e| {
+                error!("error building first facebook request: {}", e);
+                Error::FirstFacebookRequest
+            })?;
+
+        let body = call(client, req, cookie_store).await?.text().await.map_err(|e| {
+            error!("error encoding response text: {}", e);
+            Error::FirstFacebookResponse
+        })?;
+
+        let form = html::find_form(&body, &self.base_url, "locating Facebook login form", &FACEBOOK_LOGIN_FORM)
+            .map_err(|diagnostic| {
+                error!("{}", diagnostic);
+                Error::LoginForm
+            })?;
+
+        let mut fields = form.fields;
+        fields["email"] = Value::from(username.as_ref());
+        fields["pass"] = Value::from(password.as_ref());
+
+        let req = client
+            .request(Method::POST, form.action)
+            // .header("Referer", "https://www.facebook.com/")
+            // .header("Origin", "https://www.facebook.com/")
+            .header("User-Agent", USER_AGENT)
+            .header("Cookie", get_cookies(cookie_store).await)
+            .form(&fields)
+            .build()
+            .map_err(|e| {
+                error!("error building second facebook request: {}", e);
+                Error::SecondFacebookRequest
+            })?;
+
+        let res = call(client, req, cookie_store).await?;
+        if res.cookies().any(|c| c.name() == "c_user") {
+            return Ok(());
+        }
+
+        // two-factor or checkpoint pages
+        self.solve_challenges(client, cookie_store, res).await
+    }
+}
+
+impl LoginProvider for FacebookProvider<'_> {
+    fn has_session(&self, cookies: &HashMap<SmolStr, SmolStr>) -> bool {
+        cookies.contains_key("c_user")
+    }
+
+    fn login<'a>(&'a self, client: &'a Client, cookie_store: &'a CookieStore) -> LoginFuture<'a> {
+        Box::pin(self.facebook_login(client, cookie_store))
+    }
+
+    fn intel_login_url(&self, intel: &str) -> Result<Option<SmolStr>, Error> {
+        html::find_link(intel, "https://www.facebook.com/", "locating Intel's Facebook login URL").map(Some).map_err(
+            |diagnostic| {
+                error!("{}", diagnostic);
+                Error::FacebookUrl
+            },
+        )
+    }
+
+    fn login_challenge(&self) -> Option<LoginChallenge> {
+        self.last_challenge.lock().unwrap().clone()
+    }
+}
+
+/// Google login, using the no-javascript account flow
+pub struct GoogleProvider<'a> {
+    username: Option<Cow<'a, str>>,
+    password: Option<Cow<'a, str>>,
+    base_url: Cow<'a, str>,
+}
+
+impl<'a> GoogleProvider<'a> {
+    /// creates a new Google login provider
+    pub fn new(username: Option<Cow<'a, str>>, password: Option<Cow<'a, str>>) -> Self {
+        GoogleProvider { username, password, base_url: Cow::Borrowed("https://accounts.google.com") }
+    }
+
+    async fn submit_form(
+        &self,
+        client: &Client,
+        cookie_store: &CookieStore,
+        body: &str,
+        (field, value): (&str, &str),
+    ) -> Result<String, Error> {
+        let form = html::find_form(body, &self.base_url, "locating Google login form", &GOOGLE_LOGIN_FORM).map_err(
+            |diagnostic| {
+                error!("{}", diagnostic);
+                Error::LoginForm
+            },
+        )?;
+
+        let mut fields = form.fields;
+        fields[field] = Value::from(value);
+
+        let req = client
+            .request(Method::POST, form.action)
+            .header("User-Agent", USER_AGENT)
+            .header("Cookie", get_cookies(cookie_store).await)
+            .form(&fields)
+            .build()
+            .map_err(|e| {
+                error!("error building google form request: {}", e);
+                Error::GoogleRequest
+            })?;
+
+        call(client, req, cookie_store).await?.text().await.map_err(|e| {
+            error!("error encoding google form response: {}", e);
+            Error::GoogleRequest
+        })
+    }
+
+    async fn google_login(&self, client: &Client, cookie_store: &CookieStore) -> Result<(), Error> {
+        let username = self.username.as_ref().ok_or_else(|| {
+            error!("Missing google username");
+            Error::MissingGoogleUsername
+        })?;
+        let password = self.password.as_ref().ok_or_else(|| {
+            error!("Missing google password");
+            Error::MissingGooglePassword
+        })?;
+
+        let req = client
+            .request(Method::GET, format!("{}/ServiceLogin", self.base_url))
+            .header("User-Agent", USER_AGENT)
+            .build()
+            .map_err(|e| {
+                error!("error building first google request: {}", e);
+                Error::GoogleRequest
+            })?;
+        let body = call(client, req, cookie_store).await?.text().await.map_err(|e| {
+            error!("error encoding response text: {}", e);
+            Error::GoogleRequest
+        })?;
+
+        // first step submits the email, second one the password
+        let body = self.submit_form(client, cookie_store, &body, ("Email", username)).await?;
+        self.submit_form(client, cookie_store, &body, ("Passwd", password)).await?;
+
+        if !self.has_session(&*cookie_store.read().await) {
+            error!("Google login failed");
+            return Err(Error::LoginFailed);
+        }
+
+        Ok(())
+    }
+}
+
+impl LoginProvider for GoogleProvider<'_> {
+    fn has_session(&self, cookies: &HashMap<SmolStr, SmolStr>) -> bool {
+        cookies.contains_key("SID")
+    }
+
+    fn login<'a>(&'a self, client: &'a Client, cookie_store: &'a CookieStore) -> LoginFuture<'a> {
+        Box::pin(self.google_login(client, cookie_store))
+    }
+
+    fn intel_login_url(&self, intel: &str) -> Result<Option<SmolStr>, Error> {
+        html::find_link(intel, "https://accounts.google.com/", "locating Intel's Google login URL").map(Some).map_err(
+            |diagnostic| {
+                error!("{}", diagnostic);
+                Error::GoogleUrl
+            },
+        )
+    }
+}
+
+/// No login at all, Intel cookies must be added to the store
+pub struct CookiesOnlyProvider;
+
+impl LoginProvider for CookiesOnlyProvider {
+    fn has_session(&self, _cookies: &HashMap<SmolStr, SmolStr>) -> bool {
+        true
+    }
+
+    fn login<'a>(&'a self, _client: &'a Client, _cookie_store: &'a CookieStore) -> LoginFuture<'a> {
+        Box::pin(async { Ok(()) })
+    }
+
+    fn intel_login_url(&self, _intel: &str) -> Result<Option<SmolStr>, Error> {
+        Ok(None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::borrow::Cow;
+
+    use reqwest::Client;
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::TcpListener,
+    };
+
+    use super::{CookieStore, LoginChallenge, LoginProvider};
+
+    // minimal stand-in for identity providers login sequences
+    async fn stand_in(listener: TcpListener, route: fn(&str) -> (&'static str, &'static str)) {
+        loop {
+            let Ok((mut socket, _)) = listener.accept().await else {
+                return;
+            };
+            let mut buf = vec![0; 8192];
+            let mut read = 0;
+            loop {
+                read += socket.read(&mut buf[read..]).await.unwrap();
+                let request = String::from_utf8_lossy(&buf[..read]);
+                if let Some(pos) = request.find("\r\n\r\n") {
+                    let length = request
+                        .lines()
+                        .find_map(|l| l.to_lowercase().strip_prefix("content-length: ").map(|s| s.parse().unwrap()))
+                        .unwrap_or(0);
+                    if read >= pos + 4 + length {
+                        break;
+                    }
+                }
+            }
+            let request = String::from_utf8_lossy(&buf[..read]).into_owned();
+            let (cookie, body) = route(&request);
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nSet-Cookie: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                cookie,
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        }
+    }
+
+    fn google_route(request: &str) -> (&'static str, &'static str) {
+        if request.starts_with("GET /ServiceLogin ") {
+            (
+                "GAPS=1",
+                r#"<form novalidate method="post" id="gaia_loginform" action="/signin/v1/lookup"><input type="hidden" name="GALX" value="galx"><input type="email" name="Email" value=""></form>"#,
+            )
+        } else if request.starts_with("POST /signin/v1/lookup ") && request.contains("Email=agent%40example.com") {
+            (
+                "GALX=galx",
+                r#"<form novalidate method="post" id="gaia_loginform" action="/signin/challenge/sl/password"><input type="hidden" name="GALX" value="galx"><input type="password" name="Passwd"></form>"#,
+            )
+        } else if request.starts_with("POST /signin/challenge/sl/password ")
+            && request.contains("Passwd=secret")
+            && request.contains("GALX=galx")
+        {
+            ("SID=sid", "")
+        } else {
+            ("NID=1", "")
+        }
+    }
+
+    fn facebook_route(request: &str) -> (&'static str, &'static str) {
+        if request.starts_with("GET /?_fb_noscript=1 ") {
+            (
+                "datr=datr",
+                r#"<form method="post" data-testid="royal_login_form" action="/login/device-based/regular/login/?login_attempt=1&amp;lwv=110" onsubmit=""><input type="hidden" name="lsd" value="lsd"><input type="text" name="email"><input type="password" name="pass"></form>"#,
+            )
+        } else if request.starts_with("POST /login/device-based/regular/login/") && request.contains("pass=secret") {
+            (
+                "checkpoint=1",
+                r#"<form method="post" action="/checkpoint/?next"><input type="hidden" name="fb_dtsg" value="dtsg"><input type="text" name="approvals_code"><input type="submit" name="submit[Submit Code]" value="Submit Code"></form>"#,
+            )
+        } else if request.starts_with("POST /checkpoint/") && request.contains("approvals_code=123456") {
+            (
+                "checkpoint=2",
+                r#"<form method="post" action="/checkpoint/?next"><input type="hidden" name="fb_dtsg" value="dtsg"><input type="radio" name="name_action_selected" value="dont_save"><input type="submit" name="submit[Continue]" value="Continue"></form>"#,
+            )
+        } else if request.starts_with("POST /checkpoint/") && request.contains("name_action_selected=save_device") {
+            ("c_user=1", "")
+        } else {
+            ("sb=1", "")
+        }
+    }
+
+    fn facebook_checkpoint_route(request: &str) -> (&'static str, &'static str) {
+        if request.starts_with("POST /login/device-based/regular/login/") && request.contains("pass=secret") {
+            (
+                "checkpoint=1",
+                r#"<form method="post" action="/checkpoint/?review"><input type="hidden" name="fb_dtsg" value="dtsg"><input type="submit" name="submit[Continue]" value="Continue"></form>"#,
+            )
+        } else if request.starts_with("POST /checkpoint/") && request.contains("fb_dtsg=dtsg") {
+            ("c_user=1", "")
+        } else {
+            facebook_route(request)
+        }
+    }
+
+    #[tokio::test]
+    async fn google_login() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(stand_in(listener, google_route));
+
+        let mut provider =
+            super::GoogleProvider::new(Some(Cow::Borrowed("agent@example.com")), Some(Cow::Borrowed("secret")));
+        provider.base_url = Cow::Owned(base_url);
+
+        let client = Client::new();
+        let cookie_store = CookieStore::default();
+        provider.login(&client, &cookie_store).await.unwrap();
+        assert!(provider.has_session(&*cookie_store.read().await));
+    }
+
+    #[tokio::test]
+    async fn facebook_two_factor() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(stand_in(listener, facebook_route));
+
+        let client = Client::new();
+
+        let mut provider =
+            super::FacebookProvider::new(Some(Cow::Borrowed("agent@example.com")), Some(Cow::Borrowed("secret")));
+        provider.base_url = Cow::Owned(base_url.clone());
+        let cookie_store = CookieStore::default();
+        assert!(matches!(provider.login(&client, &cookie_store).await, Err(crate::Error::TwoFactorRequired)));
+
+        let mut provider =
+            super::FacebookProvider::new(Some(Cow::Borrowed("agent@example.com")), Some(Cow::Borrowed("secret")))
+                .with_challenge_handler(|challenge| async move {
+                    matches!(challenge, LoginChallenge::TwoFactor { .. }).then(|| "123456".into())
+                });
+        provider.base_url = Cow::Owned(base_url);
+        let cookie_store = CookieStore::default();
+        provider.login(&client, &cookie_store).await.unwrap();
+        assert!(provider.has_session(&*cookie_store.read().await));
+    }
+
+    #[tokio::test]
+    async fn facebook_checkpoint() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(stand_in(listener, facebook_checkpoint_route));
+
+        let client = Client::new();
+
+        let mut provider =
+            super::FacebookProvider::new(Some(Cow::Borrowed("agent@example.com")), Some(Cow::Borrowed("secret")));
+        provider.base_url = Cow::Owned(base_url.clone());
+        let cookie_store = CookieStore::default();
+        assert!(matches!(provider.login(&client, &cookie_store).await, Err(crate::Error::CheckpointRequired)));
+        assert!(matches!(
+            provider.login_challenge(),
+            Some(LoginChallenge::Checkpoint { url }) if url.contains("/login/device-based/regular/login/")
+        ));
+
+        let mut provider =
+            super::FacebookProvider::new(Some(Cow::Borrowed("agent@example.com")), Some(Cow::Borrowed("secret")))
+                .with_challenge_handler(|challenge| async move {
+                    matches!(challenge, LoginChallenge::Checkpoint { .. }).then(|| "done".into())
+                });
+        provider.base_url = Cow::Owned(base_url);
+        let cookie_store = CookieStore::default();
+        provider.login(&client, &cookie_store).await.unwrap();
+        assert!(provider.has_session(&*cookie_store.read().await));
+        assert_eq!(provider.login_challenge(), None);
+    }
+
+    #[test]
+    fn intel_login_url() {
+        let intel = r#"<a href="https://www.facebook.com/dialog/oauth?client_id=1&amp;state=2">Facebook</a><a href="https://accounts.google.com/o/oauth2/v2/auth?client_id=3&amp;state=4">Google</a>"#;
+        assert_eq!(
+            super::GoogleProvider::new(None, None).intel_login_url(intel).unwrap().unwrap(),
+            "https://accounts.google.com/o/oauth2/v2/auth?client_id=3&state=4"
+        );
+        assert_eq!(super::CookiesOnlyProvider.intel_login_url(intel).unwrap(), None);
+    }
+}
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use smol_str::SmolStr;
+
+use crate::{coords::LatLng, entities::Faction};
+
+/// getTopMissionsInBounds and getTopMissionsForPortal response root
+#[derive(Clone, Debug, Deserialize)]
+pub struct MissionsResponse {
+    /// "result" node
+    pub result: Vec<MissionSummary>,
+}
+
+/// getMissionDetails response root
+#[derive(Clone, Debug, Deserialize)]
+pub struct MissionDetailsResponse {
+    /// "result" node
+    pub result: Mission,
+}
+
+/// mission as listed by search endpoints
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct MissionSummary {
+    /// mission id
+    pub guid: SmolStr,
+    /// title
+    pub title: SmolStr,
+    /// badge image
+    pub logo_url: SmolStr,
+    /// rating, from 0 to 1
+    #[serde(deserialize_with = "deserialize_rating")]
+    pub rating: f64,
+    /// median completion time, in milliseconds
+    pub median_completion_time_ms: u64,
+}
+
+/// full mission
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Mission {
+    /// mission id
+    pub guid: SmolStr,
+    /// title
+    pub title: SmolStr,
+    /// description
+    pub description: SmolStr,
+    /// author nickname
+    pub author: SmolStr,
+    /// author faction
+    pub author_team: Faction,
+    /// rating, from 0 to 1
+    #[serde(deserialize_with = "deserialize_rating")]
+    pub rating: f64,
+    /// median completion time, in milliseconds
+    pub median_completion_time_ms: u64,
+    /// agents who completed the mission
+    pub unique_completions: u64,
+    /// waypoints order
+    pub kind: MissionKind,
+    /// waypoints
+    pub waypoints: Vec<Waypoint>,
+    /// badge image
+    pub logo_url: SmolStr,
+}
+
+impl Mission {
+    /// ids of the portals to visit, in order, hidden ones excluded
+    pub fn portal_ids(&self) -> impl Iterator<Item = &SmolStr> {
+        self.waypoints.iter().filter(|w| w.kind == WaypointKind::Portal).filter_map(|w| w.guid.as_ref())
+    }
+}
+
+/// waypoints order
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "u8")]
+pub enum MissionKind {
+    /// waypoints must be completed in order
+    Sequential,
+    /// waypoints can be completed in any order
+    NonSequential,
+    /// waypoints are revealed one at a time
+    Hidden,
+    /// unknown kind
+    Other(u8),
+}
+
+impl From<u8> for MissionKind {
+    fn from(kind: u8) -> Self {
+        match kind {
+            1 => MissionKind::Sequential,
+            2 => MissionKind::NonSequential,
+            3 => MissionKind::Hidden,
+            _ => MissionKind::Other(kind),
+        }
+    }
+}
+
+/// single mission step
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+pub struct Waypoint {
+    /// waypoint is hidden until reached
+    pub hidden: bool,
+    /// portal or field trip waypoint id, missing when hidden
+    pub guid: Option<SmolStr>,
+    /// title, missing when hidden
+    pub title: Option<SmolStr>,
+    /// waypoint kind
+    pub kind: WaypointKind,
+    /// action to perform
+    pub objective: Objective,
+    /// waypoint position, missing when hidden
+    #[serde(default, deserialize_with = "deserialize_location")]
+    pub location: Option<LatLng>,
+}
+
+/// waypoint kinds
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "u8")]
+pub enum WaypointKind {
+    /// portal
+    Portal,
+    /// field trip waypoint
+    FieldTrip,
+    /// unknown kind
+    Other(u8),
+}
+
+impl From<u8> for WaypointKind {
+    fn from(kind: u8) -> Self {
+        match kind {
+            1 => WaypointKind::Portal,
+            2 => WaypointKind::FieldTrip,
+            _ => WaypointKind::Other(kind),
+        }
+    }
+}
+
+/// waypoint objectives
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "u8")]
+pub enum Objective {
+    /// hack the portal
+    Hack,
+    /// capture or upgrade the portal
+    CaptureOrUpgrade,
+    /// create a link from the portal
+    CreateLink,
+    /// create a field from the portal
+    CreateField,
+    /// install a mod on the portal
+    InstallMod,
+    /// take a photo
+    TakePhoto,
+    /// view the field trip waypoint
+    ViewWaypoint,
+    /// enter the passphrase
+    EnterPassphrase,
+    /// unknown objective
+    Other(u8),
+}
+
+impl From<u8> for Objective {
+    fn from(objective: u8) -> Self {
+        match objective {
+            1 => Objective::Hack,
+            2 => Objective::CaptureOrUpgrade,
+            3 => Objective::CreateLink,
+            4 => Objective::CreateField,
+            5 => Objective::InstallMod,
+            6 => Objective::TakePhoto,
+            7 => Objective::ViewWaypoint,
+            8 => Objective::EnterPassphrase,
+            _ => Objective::Other(objective),
+        }
+    }
+}
+
+fn deserialize_rating<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    let rating_e6 = u64::deserialize(deserializer)?;
+    Ok(rating_e6 as f64 / 1000000_f64)
+}
+
+// point data is a portal-like array for portals, and a shorter one without team for field trip waypoints
+fn deserialize_location<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<LatLng>, D::Error> {
+    let data = Option::<Vec<Value>>::deserialize(deserializer)?;
+    Ok(data.and_then(|data| {
+        let offset = if data.first()?.as_str()? == "f" { 1 } else { 2 };
+        Some(LatLng::from_e6(data.get(offset)?.as_i64()?, data.get(offset + 1)?.as_i64()?))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{MissionKind, Objective, WaypointKind};
+
+    #[test]
+    fn top_missions() {
+        let res: super::MissionsResponse = serde_json::from_str(r#"{"result":[["0f2d7a.1c","Padova 1/6","https://lh3.googleusercontent.com/logo",985000,2452000],["1a3c9f.1c","Padova 2/6","https://lh3.googleusercontent.com/logo2",1000000,3100000]]}"#).unwrap();
+        assert_eq!(res.result.len(), 2);
+        assert_eq!(res.result[0].title, "Padova 1/6");
+        assert_eq!(res.result[0].rating, 0.985);
+        assert_eq!(res.result[1].median_completion_time_ms, 3100000);
+    }
+
+    #[test]
+    fn mission_details() {
+        let res: super::MissionDetailsResponse = serde_json::from_str(r#"{"result":["0f2d7a.1c","Padova 1/6","Walk around the center","TerminateThis","RESISTANCE",985000,2452000,321,1,[[false,"a.16","Fontana",1,1,["p","R",45406674,11875542]],[false,"b.16","Generale tedesco",1,6,["p","E",45406000,11875000]],[true,null,null,1,2,null],[false,"c.1c","Piazza",2,7,["f",45400000,11870000]]],"https://lh3.googleusercontent.com/logo"]}"#).unwrap();
+        let mission = res.result;
+        assert_eq!(mission.author, "TerminateThis");
+        assert_eq!(mission.kind, MissionKind::Sequential);
+        assert_eq!(mission.unique_completions, 321);
+        assert_eq!(mission.waypoints[1].objective, Objective::TakePhoto);
+        assert_eq!(mission.waypoints[0].location.unwrap().lat_e6(), 45406674);
+        assert!(mission.waypoints[2].hidden && mission.waypoints[2].location.is_none());
+        assert_eq!(mission.waypoints[3].kind, WaypointKind::FieldTrip);
+        assert_eq!(mission.waypoints[3].location.unwrap().lng_e6(), 11870000);
+        assert_eq!(mission.portal_ids().collect::<Vec<_>>(), ["a.16", "b.16"]);
+    }
+}
@@ -0,0 +1,142 @@
+use serde::Deserialize;
+use smol_str::SmolStr;
+
+use crate::{
+    entities::Rarity,
+    utils::{deserialize_number, deserialize_number_opt},
+};
+
+/// passcode rewards
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+pub struct Reward {
+    /// AP gained
+    #[serde(default, deserialize_with = "deserialize_number")]
+    pub ap: u64,
+    /// XM gained
+    #[serde(default, deserialize_with = "deserialize_number")]
+    pub xm: u64,
+    /// items gained
+    #[serde(default, rename = "inventory", deserialize_with = "deserialize_items")]
+    pub items: Vec<RewardItem>,
+    /// other rewards, as display text
+    #[serde(default)]
+    pub other: Vec<SmolStr>,
+}
+
+/// single item kind gained
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RewardItem {
+    /// item name
+    pub name: SmolStr,
+    /// item level, for leveled items
+    pub level: Option<u8>,
+    /// item rarity, for mods and other rarity based items
+    pub rarity: Option<Rarity>,
+    /// quantity
+    pub count: u32,
+}
+
+/// passcode redemption failures
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum RedeemError {
+    /// passcode has already been redeemed by this agent
+    #[error("passcode already redeemed")]
+    AlreadyRedeemed,
+    /// passcode doesn't exist
+    #[error("invalid passcode")]
+    Invalid,
+    /// passcode has reached its redemption limit
+    #[error("passcode fully redeemed")]
+    FullyRedeemed,
+    /// any other failure, with Intel message
+    #[error("passcode redemption failed: {0}")]
+    Other(SmolStr),
+}
+
+impl From<SmolStr> for RedeemError {
+    fn from(message: SmolStr) -> Self {
+        let lower = message.to_lowercase();
+        if lower.contains("already redeemed") {
+            RedeemError::AlreadyRedeemed
+        } else if lower.contains("fully redeemed") {
+            RedeemError::FullyRedeemed
+        } else if lower.contains("invalid") {
+            RedeemError::Invalid
+        } else {
+            RedeemError::Other(message)
+        }
+    }
+}
+
+/// endpoint response root
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct IntelResponse {
+    error: Option<SmolStr>,
+    #[serde(alias = "result")]
+    rewards: Option<Reward>,
+}
+
+impl IntelResponse {
+    pub(crate) fn into_result(self) -> Result<Reward, RedeemError> {
+        match (self.error, self.rewards) {
+            (Some(error), _) => Err(error.into()),
+            (None, Some(rewards)) => Ok(rewards),
+            (None, None) => Err(RedeemError::Other("empty response".into())),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawItem {
+    name: SmolStr,
+    awards: Vec<RawAward>,
+}
+
+#[derive(Deserialize)]
+struct RawAward {
+    #[serde(default, deserialize_with = "deserialize_number_opt")]
+    level: Option<u8>,
+    rarity: Option<Rarity>,
+    #[serde(deserialize_with = "deserialize_number")]
+    count: u32,
+}
+
+fn deserialize_items<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Vec<RewardItem>, D::Error> {
+    Ok(Vec::<RawItem>::deserialize(deserializer)?
+        .into_iter()
+        .flat_map(|item| {
+            item.awards.into_iter().map(move |award| RewardItem {
+                name: item.name.clone(),
+                level: award.level.filter(|level| *level > 0),
+                rarity: award.rarity,
+                count: award.count,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IntelResponse, RedeemError, RewardItem};
+    use crate::entities::Rarity;
+
+    #[test]
+    fn redeem() {
+        let res: IntelResponse = serde_json::from_str(r#"{"rewards":{"ap":"1000","xm":"2500","other":["Media"],"inventory":[{"name":"Resonator","awards":[{"level":8,"count":5},{"level":7,"count":2}]},{"name":"Portal Shield","awards":[{"level":0,"rarity":"VERY_RARE","count":1}]}]}}"#).unwrap();
+        let reward = res.into_result().unwrap();
+        assert_eq!(reward.ap, 1000);
+        assert_eq!(reward.xm, 2500);
+        assert_eq!(reward.items.len(), 3);
+        assert_eq!(
+            reward.items[2],
+            RewardItem { name: "Portal Shield".into(), level: None, rarity: Some(Rarity::VeryRare), count: 1 }
+        );
+
+        let res: IntelResponse = serde_json::from_str(r#"{"error":"Passcode already redeemed."}"#).unwrap();
+        assert_eq!(res.into_result(), Err(RedeemError::AlreadyRedeemed));
+        let res: IntelResponse = serde_json::from_str(r#"{"error":"Invalid passcode."}"#).unwrap();
+        assert_eq!(res.into_result(), Err(RedeemError::Invalid));
+        let res: IntelResponse = serde_json::from_str(r#"{"error":"Passcode fully redeemed."}"#).unwrap();
+        assert_eq!(res.into_result(), Err(RedeemError::FullyRedeemed));
+    }
+}
@@ -0,0 +1,673 @@
+use std::collections::{HashMap, HashSet};
+
+use smol_str::SmolStr;
+
+use crate::{
+    coords::LatLng,
+    entities::{Entity, Faction, IntelEntity, IntelField, IntelLink, IntelPortal},
+    geo,
+};
+
+/// planned link endpoint
+#[derive(Clone, Debug, PartialEq)]
+pub enum LinkEndpoint<'a> {
+    /// portal id, to be found into scan results
+    Portal(&'a str),
+    /// raw coordinates
+    Coords(LatLng),
+}
+
+#[derive(Clone, Debug)]
+struct ResolvedEndpoint {
+    id: Option<SmolStr>,
+    coords: LatLng,
+}
+
+impl ResolvedEndpoint {
+    fn resolve(endpoint: &LinkEndpoint<'_>, entities: &[&IntelEntity]) -> Option<Self> {
+        match endpoint {
+            LinkEndpoint::Portal(id) => entities.iter().find_map(|e| {
+                let portal = e.as_portal().filter(|p| p.id == *id)?;
+                Some(ResolvedEndpoint { id: Some(portal.id.clone()), coords: portal.entity.coords() })
+            }),
+            LinkEndpoint::Coords(coords) => Some(ResolvedEndpoint { id: None, coords: *coords }),
+        }
+    }
+
+    fn is(&self, portal_id: &str) -> bool {
+        self.id.as_deref() == Some(portal_id)
+    }
+}
+
+/// outcome of a planned link check
+#[derive(Clone, Debug)]
+pub struct LinkCheck<'a> {
+    /// existing links crossing the planned one
+    pub blocking_links: Vec<&'a Entity<IntelLink>>,
+    /// existing fields covering the origin portal
+    pub covering_fields: Vec<&'a Entity<IntelField>>,
+}
+
+impl LinkCheck<'_> {
+    /// checks if nothing prevents the link from being thrown
+    pub fn is_possible(&self) -> bool {
+        self.blocking_links.is_empty() && self.covering_fields.is_empty()
+    }
+}
+
+/// checks a planned link against scan results, returns `None` if a portal endpoint isn't part of the scan
+pub fn check_link<'a, I>(origin: LinkEndpoint<'_>, destination: LinkEndpoint<'_>, entities: I) -> Option<LinkCheck<'a>>
+where
+    I: IntoIterator<Item = &'a IntelEntity>,
+{
+    let entities = entities.into_iter().collect::<Vec<_>>();
+    let origin = ResolvedEndpoint::resolve(&origin, &entities)?;
+    let destination = ResolvedEndpoint::resolve(&destination, &entities)?;
+
+    let blocking_links = entities
+        .iter()
+        .filter_map(|e| e.as_link())
+        .filter(|link| {
+            let l = &link.entity;
+            let shares_portal = [&l.origin_portal_id, &l.destination_portal_id]
+                .into_iter()
+                .any(|id| origin.is(id) || destination.is(id));
+            !shares_portal && geo::segments_cross(origin.coords, destination.coords, l.origin(), l.destination())
+        })
+        .collect();
+
+    let covering_fields = entities
+        .iter()
+        .filter_map(|e| e.as_field())
+        .filter(|field| {
+            let f = &field.entity;
+            !f.portals.iter().any(|p| origin.is(&p.id)) && f.contains(origin.coords)
+        })
+        .collect();
+
+    Some(LinkCheck { blocking_links, covering_fields })
+}
+
+const BASE_OUTBOUND_LINKS: usize = 8;
+const MAX_OUTBOUND_LINKS: usize = 40;
+
+/// field planner errors
+#[derive(Debug, thiserror::Error)]
+pub enum PlanError {
+    /// portal isn't part of the planner
+    #[error("Unknown portal {0}")]
+    UnknownPortal(SmolStr),
+    /// anchors must be distinct
+    #[error("Duplicated anchor {0}")]
+    DuplicatedAnchor(SmolStr),
+}
+
+/// portal as seen by the planner
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlanPortal {
+    /// portal id
+    pub id: SmolStr,
+    /// latitude
+    pub latitude: f64,
+    /// longitude
+    pub longitude: f64,
+    /// faction
+    pub faction: Faction,
+    /// link range in meters, zero when the portal can't throw links
+    pub link_range: f64,
+    /// portal has resonators, so it can be a link destination
+    pub linkable: bool,
+    /// max outbound links
+    pub outbound_limit: usize,
+}
+
+impl PlanPortal {
+    /// builds a planner portal, link range is approximated from portal level when resonators are unknown
+    pub fn new(id: SmolStr, portal: &IntelPortal) -> Self {
+        let link_range = portal.link_range().unwrap_or_else(|| {
+            // portal level is the floored resonators average, so this is a lower bound
+            if portal.res_count < 8 { 0_f64 } else { 160_f64 * f64::from(portal.level).powi(4) }
+        });
+        let bonus = portal
+            .mods
+            .iter()
+            .flatten()
+            .flatten()
+            .filter_map(|m| m.stats.outgoing_links_bonus)
+            .map(|b| b as usize)
+            .sum::<usize>();
+        PlanPortal {
+            id,
+            latitude: portal.latitude,
+            longitude: portal.longitude,
+            faction: portal.faction,
+            link_range,
+            linkable: portal.res_count > 0 && !portal.faction.is_neutral(),
+            outbound_limit: (BASE_OUTBOUND_LINKS + bonus).min(MAX_OUTBOUND_LINKS),
+        }
+    }
+
+    fn coords(&self) -> LatLng {
+        LatLng::new(self.latitude, self.longitude)
+    }
+}
+
+/// planned link
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PlannedLink {
+    /// origin portal id
+    pub origin: SmolStr,
+    /// destination portal id
+    pub destination: SmolStr,
+    /// fields created by the link
+    pub fields: u8,
+}
+
+/// reason why a candidate link has been left out of the plan
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SkipReason {
+    /// portals are already linked
+    Duplicate,
+    /// origin or destination portal belongs to another faction
+    EnemyPortal,
+    /// origin portal has no outbound links left
+    OutboundLimit,
+    /// destination portal has no resonators
+    UnlinkableDestination,
+    /// destination is out of origin's range
+    OutOfRange,
+    /// origin portal lies under a field
+    OriginCovered,
+    /// link would cross an existing or planned link
+    Crossing,
+}
+
+/// ordered link plan
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LinkPlan {
+    /// links, in the order they have to be thrown
+    pub links: Vec<PlannedLink>,
+    /// candidate links left out of the plan, as (origin, destination, reason)
+    pub skipped: Vec<(SmolStr, SmolStr, SkipReason)>,
+}
+
+impl LinkPlan {
+    /// total created fields
+    pub fn fields(&self) -> usize {
+        self.links.iter().map(|l| usize::from(l.fields)).sum()
+    }
+}
+
+#[derive(Clone)]
+struct Segment {
+    ids: [SmolStr; 2],
+    from: LatLng,
+    to: LatLng,
+}
+
+#[derive(Clone)]
+struct Triangle {
+    ids: [SmolStr; 3],
+    vertices: [LatLng; 3],
+}
+
+impl Triangle {
+    fn covers(&self, portal: &PlanPortal) -> bool {
+        let [a, b, c] = self.vertices;
+        !self.ids.contains(&portal.id) && geo::triangle_contains(a, b, c, portal.coords())
+    }
+}
+
+/// multi-layer field planner over scan results
+#[derive(Clone)]
+pub struct FieldPlanner {
+    faction: Faction,
+    portals: HashMap<SmolStr, PlanPortal>,
+    links: Vec<Segment>,
+    fields: Vec<Triangle>,
+}
+
+impl FieldPlanner {
+    /// creates a planner from scan results for given faction, existing links block the plan and existing fields
+    /// cover portals, only the faction portals can throw and receive links
+    pub fn new<'a, I>(faction: Faction, entities: I) -> Self
+    where
+        I: IntoIterator<Item = &'a IntelEntity>,
+    {
+        let mut planner = FieldPlanner { faction, portals: HashMap::new(), links: Vec::new(), fields: Vec::new() };
+        for entity in entities {
+            match entity {
+                IntelEntity::Portal(p) => {
+                    planner.portals.insert(p.id.clone(), PlanPortal::new(p.id.clone(), &p.entity));
+                }
+                IntelEntity::Link(l) => planner.links.push(Segment {
+                    ids: [l.entity.origin_portal_id.clone(), l.entity.destination_portal_id.clone()],
+                    from: l.entity.origin(),
+                    to: l.entity.destination(),
+                }),
+                IntelEntity::Field(f) => {
+                    let [a, b, c] = &f.entity.portals;
+                    planner.fields.push(Triangle {
+                        ids: [a.id.clone(), b.id.clone(), c.id.clone()],
+                        vertices: [a.coords(), b.coords(), c.coords()],
+                    })
+                }
+                IntelEntity::Other(_) => {}
+            }
+        }
+        planner
+    }
+
+    /// refines a portal with its details, giving exact link range and outbound limit
+    pub fn with_portal_details(mut self, id: &SmolStr, portal: &IntelPortal) -> Self {
+        self.portals.insert(id.clone(), PlanPortal::new(id.clone(), portal));
+        self
+    }
+
+    /// planner portals
+    pub fn portals(&self) -> impl Iterator<Item = &PlanPortal> {
+        self.portals.values()
+    }
+
+    fn portal(&self, id: &str) -> Result<&PlanPortal, PlanError> {
+        self.portals.get(id).ok_or_else(|| PlanError::UnknownPortal(id.into()))
+    }
+
+    fn anchors<'a, const N: usize>(&'a self, ids: [&str; N]) -> Result<[&'a PlanPortal; N], PlanError> {
+        for (i, id) in ids.iter().enumerate() {
+            if ids[..i].contains(id) {
+                return Err(PlanError::DuplicatedAnchor((*id).into()));
+            }
+        }
+        let mut anchors = Vec::with_capacity(N);
+        for id in ids {
+            anchors.push(self.portal(id)?);
+        }
+        Ok(anchors.try_into().unwrap_or_else(|_| unreachable!()))
+    }
+
+    /// fan field: every other portal links to the anchor and to the previous portals, sweeping around the anchor
+    pub fn fan(&self, anchor: &str) -> Result<LinkPlan, PlanError> {
+        let [anchor] = self.anchors([anchor])?;
+
+        let mut targets = self
+            .portals
+            .values()
+            .filter(|p| p.id != anchor.id)
+            .map(|p| (geo::bearing(anchor.coords(), p.coords()), p))
+            .collect::<Vec<_>>();
+        targets.sort_unstable_by(|(a, _), (b, _)| a.total_cmp(b));
+        // the sweep starts after the widest angular gap, so that fields never wrap around the anchor
+        if let Some(start) = (0..targets.len()).max_by(|&i, &j| {
+            let gap =
+                |k: usize| (targets[k].0 - targets[(k + targets.len() - 1) % targets.len()].0 + 360_f64) % 360_f64;
+            gap(i).total_cmp(&gap(j))
+        }) {
+            targets.rotate_left(start);
+        }
+
+        let mut simulation = Simulation::new(self);
+        for (i, (_, target)) in targets.iter().enumerate() {
+            simulation.link(target, anchor);
+            // portals out of range are left out upfront, keeping big scans manageable
+            let in_range = |p: &&PlanPortal| geo::distance(target.coords(), p.coords()) <= target.link_range;
+            for (_, previous) in targets[..i].iter().rev().filter(|(_, p)| in_range(p)) {
+                simulation.link(target, previous);
+            }
+        }
+        Ok(simulation.plan)
+    }
+
+    /// onion field: stacked layers over the same base, each apex covering the previous one
+    pub fn onion(&self, base_a: &str, base_b: &str) -> Result<LinkPlan, PlanError> {
+        let [a, b] = self.anchors([base_a, base_b])?;
+
+        let mut simulation = Simulation::new(self);
+        simulation.link(a, b);
+        for left in [true, false] {
+            let mut apexes = self
+                .portals
+                .values()
+                .filter(|p| p.id != a.id && p.id != b.id)
+                .filter(|p| (geo::side(a.coords(), b.coords(), p.coords()) > 0_f64) == left)
+                .map(|p| (geo::triangle_area(a.coords(), b.coords(), p.coords()), p))
+                .collect::<Vec<_>>();
+            apexes.sort_unstable_by(|(x, _), (y, _)| x.total_cmp(y));
+
+            let mut last: Option<&PlanPortal> = None;
+            for (_, apex) in apexes {
+                if last.is_some_and(|l| !geo::triangle_contains(a.coords(), b.coords(), apex.coords(), l.coords())) {
+                    continue;
+                }
+                if simulation.link_all(&[(apex, a), (apex, b)]) {
+                    last = Some(apex);
+                }
+            }
+        }
+        Ok(simulation.plan)
+    }
+
+    /// homogeneous field: the triangle is recursively split on the portal closest to each centroid
+    pub fn homogeneous(&self, a: &str, b: &str, c: &str, depth: u8) -> Result<LinkPlan, PlanError> {
+        let anchors = self.anchors([a, b, c])?;
+
+        // (split portal, triangle corners, depth)
+        let mut splits = Vec::new();
+        self.split(anchors, depth, 1, &mut splits);
+
+        let is_anchor = |p: &PlanPortal| anchors.iter().any(|a| a.id == p.id);
+
+        // anchors link inner portals last, closing two layers per link, anchors that would run out of outbound
+        // links are linked by their deepest inner portals instead, before these get covered
+        splits.sort_by_key(|(_, _, level)| std::cmp::Reverse(*level));
+        let mut inward = HashSet::new();
+        for anchor in anchors {
+            let linking = splits.iter().filter(|(_, corners, _)| corners.iter().any(|c| c.id == anchor.id));
+            // one link is needed for the outer field
+            let excess = linking.clone().count().saturating_sub(anchor.outbound_limit.saturating_sub(1));
+            inward.extend(linking.take(excess).map(|(portal, _, _)| (&portal.id, &anchor.id)));
+        }
+        let thrown_by_inner =
+            |portal: &PlanPortal, corner: &PlanPortal| !is_anchor(corner) || inward.contains(&(&portal.id, &corner.id));
+
+        let mut simulation = Simulation::new(self);
+        // inner portals link each other before getting covered, deepest first
+        for (portal, corners, _) in &splits {
+            for corner in corners.iter().filter(|c| thrown_by_inner(portal, c)) {
+                simulation.link(portal, corner);
+            }
+        }
+        // outer field
+        simulation.link(anchors[0], anchors[1]);
+        simulation.link(anchors[1], anchors[2]);
+        simulation.link(anchors[2], anchors[0]);
+        // anchors link inner portals, shallowest first
+        splits.sort_by_key(|(_, _, level)| *level);
+        for (portal, corners, _) in &splits {
+            for corner in corners.iter().filter(|c| !thrown_by_inner(portal, c)) {
+                simulation.link(corner, portal);
+            }
+        }
+        Ok(simulation.plan)
+    }
+
+    fn split<'a>(
+        &'a self,
+        corners: [&'a PlanPortal; 3],
+        depth: u8,
+        level: u8,
+        splits: &mut Vec<(&'a PlanPortal, [&'a PlanPortal; 3], u8)>,
+    ) {
+        if depth == 0 {
+            return;
+        }
+        let [a, b, c] = corners.map(PlanPortal::coords);
+        let centroid = geo::triangle_centroid(a, b, c);
+        let Some(inner) = self
+            .portals
+            .values()
+            .filter(|p| corners.iter().all(|c| c.id != p.id) && geo::triangle_contains(a, b, c, p.coords()))
+            .min_by(|x, y| geo::distance(x.coords(), centroid).total_cmp(&geo::distance(y.coords(), centroid)))
+        else {
+            return;
+        };
+        splits.push((inner, corners, level));
+        let [x, y, z] = corners;
+        for sub in [[x, y, inner], [y, z, inner], [z, x, inner]] {
+            self.split(sub, depth - 1, level + 1, splits);
+        }
+    }
+}
+
+/// applies links one by one, enforcing game rules
+#[derive(Clone)]
+struct Simulation<'a> {
+    planner: &'a FieldPlanner,
+    outbound: HashMap<SmolStr, usize>,
+    adjacent: HashMap<SmolStr, HashSet<SmolStr>>,
+    links: Vec<Segment>,
+    fields: Vec<Triangle>,
+    plan: LinkPlan,
+}
+
+impl<'a> Simulation<'a> {
+    fn new(planner: &'a FieldPlanner) -> Self {
+        Simulation {
+            planner,
+            outbound: HashMap::new(),
+            adjacent: HashMap::new(),
+            links: Vec::new(),
+            fields: Vec::new(),
+            plan: LinkPlan::default(),
+        }
+    }
+
+    fn linked(&self, a: &SmolStr, b: &SmolStr) -> bool {
+        self.adjacent.get(a).is_some_and(|adjacent| adjacent.contains(b))
+    }
+
+    fn check(&self, origin: &PlanPortal, destination: &PlanPortal) -> Result<(), SkipReason> {
+        if self.linked(&origin.id, &destination.id) {
+            return Err(SkipReason::Duplicate);
+        }
+        // neutral destinations are reported as unlinkable
+        let enemy = |p: &PlanPortal| p.faction != self.planner.faction && !p.faction.is_neutral();
+        if enemy(origin) || enemy(destination) {
+            return Err(SkipReason::EnemyPortal);
+        }
+        if self.outbound.get(&origin.id).copied().unwrap_or_default() >= origin.outbound_limit {
+            return Err(SkipReason::OutboundLimit);
+        }
+        if !destination.linkable {
+            return Err(SkipReason::UnlinkableDestination);
+        }
+        if geo::distance(origin.coords(), destination.coords()) > origin.link_range {
+            return Err(SkipReason::OutOfRange);
+        }
+        if self.planner.fields.iter().chain(&self.fields).any(|f| f.covers(origin)) {
+            return Err(SkipReason::OriginCovered);
+        }
+        let crosses = self.planner.links.iter().chain(&self.links).any(|l| {
+            !l.ids.contains(&origin.id)
+                && !l.ids.contains(&destination.id)
+                && geo::segments_cross(origin.coords(), destination.coords(), l.from, l.to)
+        });
+        if crosses {
+            return Err(SkipReason::Crossing);
+        }
+        Ok(())
+    }
+
+    fn link(&mut self, origin: &PlanPortal, destination: &PlanPortal) -> bool {
+        if let Err(reason) = self.check(origin, destination) {
+            self.plan.skipped.push((origin.id.clone(), destination.id.clone(), reason));
+            return false;
+        }
+
+        // a link creates at most one field per side, the largest one
+        let (from, to) = (origin.coords(), destination.coords());
+        let mut best: [Option<(f64, &PlanPortal)>; 2] = [None, None];
+        let thirds = self.adjacent.get(&origin.id).into_iter().flatten().filter(|id| self.linked(&destination.id, id));
+        for third in thirds.filter_map(|id| self.planner.portals.get(id)) {
+            let side = usize::from(geo::side(from, to, third.coords()) > 0_f64);
+            let area = geo::triangle_area(from, to, third.coords());
+            if best[side].is_none_or(|(best_area, _)| area > best_area) {
+                best[side] = Some((area, third));
+            }
+        }
+        let created = best.iter().flatten().count();
+        for (_, third) in best.into_iter().flatten() {
+            self.fields.push(Triangle {
+                ids: [origin.id.clone(), destination.id.clone(), third.id.clone()],
+                vertices: [from, to, third.coords()],
+            });
+        }
+
+        self.links.push(Segment { ids: [origin.id.clone(), destination.id.clone()], from, to });
+        self.adjacent.entry(origin.id.clone()).or_default().insert(destination.id.clone());
+        self.adjacent.entry(destination.id.clone()).or_default().insert(origin.id.clone());
+        *self.outbound.entry(origin.id.clone()).or_default() += 1;
+        self.plan.links.push(PlannedLink {
+            origin: origin.id.clone(),
+            destination: destination.id.clone(),
+            fields: created as u8,
+        });
+        true
+    }
+
+    /// applies all links or none of them
+    fn link_all(&mut self, links: &[(&PlanPortal, &PlanPortal)]) -> bool {
+        let snapshot = self.clone();
+        for (origin, destination) in links {
+            if !self.link(origin, destination) {
+                let skipped = self.plan.skipped.pop();
+                *self = snapshot;
+                self.plan.skipped.extend(skipped);
+                return false;
+            }
+        }
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LinkEndpoint, SkipReason};
+    use crate::{
+        coords::LatLng,
+        entities::{Faction, IntelEntity},
+    };
+
+    fn entities() -> Vec<IntelEntity> {
+        serde_json::from_str(
+            r#"[
+                ["a.16",1761242200427,["p","E",45000000,12000000,5,85,8,null,"A",[],false,false,null,1761242200427,null,null,null,null,0]],
+                ["b.16",1761242200427,["p","E",45010000,12010000,5,85,8,null,"B",[],false,false,null,1761242200427,null,null,null,null,0]],
+                ["c.16",1761242200427,["p","R",45000000,12010000,5,85,8,null,"C",[],false,false,null,1761242200427,null,null,null,null,0]],
+                ["d.16",1761242200427,["p","R",45010000,12000000,5,85,8,null,"D",[],false,false,null,1761242200427,null,null,null,null,0]],
+                ["e.16",1761242200427,["p","N",45004000,12005000,1,0,0,null,"E",[],false,false,null,1761242200427,null,null,null,null,0]],
+                ["x.9",1761242200427,["e","R","c.16",45000000,12010000,"d.16",45010000,12000000]],
+                ["y.b",1761242200427,["r","R",[["c.16",45000000,12010000],["d.16",45010000,12000000],["a.16",45000000,12000000]]]]
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn check_link() {
+        let entities = entities();
+
+        let check = super::check_link(LinkEndpoint::Portal("a.16"), LinkEndpoint::Portal("b.16"), &entities).unwrap();
+        assert_eq!(check.blocking_links.len(), 1);
+        assert!(check.covering_fields.is_empty());
+        assert!(!check.is_possible());
+
+        // links sharing a portal never block
+        let check = super::check_link(LinkEndpoint::Portal("c.16"), LinkEndpoint::Portal("a.16"), &entities).unwrap();
+        assert!(check.is_possible());
+
+        let check =
+            super::check_link(LinkEndpoint::Portal("e.16"), LinkEndpoint::Coords(LatLng::new(45.0, 12.0)), &entities)
+                .unwrap();
+        assert_eq!(check.covering_fields.len(), 1);
+
+        assert!(super::check_link(LinkEndpoint::Portal("z.16"), LinkEndpoint::Portal("a.16"), &entities).is_none());
+    }
+
+    fn portals() -> Vec<IntelEntity> {
+        serde_json::from_str(
+            r#"[
+                ["a.16",1761242200427,["p","E",45000000,12000000,8,100,8,null,"A",[],false,false,null,1761242200427,null,null,null,null,0]],
+                ["b.16",1761242200427,["p","E",45000000,12020000,8,100,8,null,"B",[],false,false,null,1761242200427,null,null,null,null,0]],
+                ["c.16",1761242200427,["p","E",45020000,12010000,8,100,8,null,"C",[],false,false,null,1761242200427,null,null,null,null,0]],
+                ["d.16",1761242200427,["p","E",45006000,12010000,8,100,8,null,"D",[],false,false,null,1761242200427,null,null,null,null,0]],
+                ["e.16",1761242200427,["p","E",45002000,12008000,8,100,8,null,"E",[],false,false,null,1761242200427,null,null,null,null,0]]
+            ]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fan() {
+        let mut entities = portals();
+        // neutral portals can neither throw nor receive links
+        entities.extend(serde_json::from_str::<Vec<IntelEntity>>(r#"[["f.16",1761242200427,["p","N",45010000,11995000,1,0,0,null,"F",[],false,false,null,1761242200427,null,null,null,null,0]]]"#).unwrap());
+        let plan = super::FieldPlanner::new(Faction::Enlightened, &entities).fan("a.16").unwrap();
+        let links =
+            plan.links.iter().map(|l| (l.origin.as_str(), l.destination.as_str(), l.fields)).collect::<Vec<_>>();
+        assert_eq!(
+            links,
+            [
+                ("c.16", "a.16", 0),
+                ("d.16", "a.16", 0),
+                ("d.16", "c.16", 1),
+                ("e.16", "a.16", 0),
+                ("e.16", "d.16", 1),
+                ("b.16", "a.16", 0),
+                ("b.16", "e.16", 1),
+                ("b.16", "d.16", 1),
+                ("b.16", "c.16", 1),
+            ]
+        );
+        let skipped = plan.skipped.iter().map(|(o, d, reason)| (o.as_str(), d.as_str(), *reason)).collect::<Vec<_>>();
+        assert_eq!(
+            skipped,
+            [
+                ("f.16", "a.16", SkipReason::OutOfRange),
+                ("c.16", "f.16", SkipReason::UnlinkableDestination),
+                ("d.16", "f.16", SkipReason::UnlinkableDestination),
+                // d lies between e and c
+                ("e.16", "c.16", SkipReason::Crossing),
+                ("e.16", "f.16", SkipReason::UnlinkableDestination),
+                ("b.16", "f.16", SkipReason::UnlinkableDestination),
+            ]
+        );
+    }
+
+    #[test]
+    fn enemy_portals() {
+        let mut entities = portals();
+        entities.extend(serde_json::from_str::<Vec<IntelEntity>>(r#"[["r.16",1761242200427,["p","R",45004000,12016000,8,100,8,null,"R",[],false,false,null,1761242200427,null,null,null,null,0]]]"#).unwrap());
+        let planner = super::FieldPlanner::new(Faction::Enlightened, &entities);
+
+        // enemy targets are skipped, both as origins and as destinations
+        let plan = planner.fan("a.16").unwrap();
+        assert!(plan.links.iter().all(|l| l.origin != "r.16" && l.destination != "r.16"), "{plan:#?}");
+        assert!(plan.skipped.contains(&("r.16".into(), "a.16".into(), SkipReason::EnemyPortal)));
+
+        // nothing links to an enemy anchor
+        let plan = planner.fan("r.16").unwrap();
+        assert!(plan.links.iter().all(|l| l.destination != "r.16"), "{plan:#?}");
+        assert!(plan.skipped.contains(&("a.16".into(), "r.16".into(), SkipReason::EnemyPortal)));
+    }
+
+    #[test]
+    fn onion() {
+        let plan = super::FieldPlanner::new(Faction::Enlightened, &portals()).onion("a.16", "b.16").unwrap();
+        // base link, then e, d and c stacked on top of each other
+        assert_eq!(plan.links.len(), 7, "{plan:#?}");
+        assert_eq!(plan.fields(), 3);
+    }
+
+    #[test]
+    fn homogeneous() {
+        let planner = super::FieldPlanner::new(Faction::Enlightened, &portals());
+        let plan = planner.homogeneous("a.16", "b.16", "c.16", 2).unwrap();
+        // outer field, three fields around d, three fields around e
+        assert_eq!(plan.fields(), 7, "{plan:#?}");
+        assert!(plan.skipped.is_empty());
+
+        // anchors without outbound links to spare only throw the outer field
+        let mut planner = planner;
+        for anchor in ["a.16", "b.16", "c.16"] {
+            planner.portals.get_mut(anchor).unwrap().outbound_limit = 1;
+        }
+        let plan = planner.homogeneous("a.16", "b.16", "c.16", 2).unwrap();
+        assert!(plan.skipped.is_empty(), "{plan:#?}");
+        assert_eq!(plan.links.iter().filter(|l| l.origin.starts_with(['a', 'b', 'c'])).count(), 3);
+        assert_eq!(plan.links.len(), 9);
+
+        assert!(matches!(planner.fan("z.16"), Err(super::PlanError::UnknownPortal(_))));
+        assert!(matches!(planner.onion("a.16", "a.16"), Err(super::PlanError::DuplicatedAnchor(_))));
+    }
+}
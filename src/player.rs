@@ -0,0 +1,72 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use smol_str::SmolStr;
+use tracing::warn;
+
+use crate::{
+    entities::Faction,
+    utils::{deserialize_number, deserialize_number_opt},
+};
+
+pub(crate) static PLAYER: Lazy<Regex> = Lazy::new(|| Regex::new(r"var PLAYER\s*=\s*(\{[\s\S]*?\});").unwrap());
+
+/// logged in agent profile, as embedded into the Intel dashboard page
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct Player {
+    /// agent nickname
+    pub nickname: SmolStr,
+    /// agent faction
+    pub team: Faction,
+    /// agent level
+    #[serde(deserialize_with = "deserialize_number")]
+    pub level: u8,
+    /// agent verified level
+    #[serde(default, deserialize_with = "deserialize_number_opt")]
+    pub verified_level: Option<u8>,
+    /// agent AP
+    #[serde(deserialize_with = "deserialize_number")]
+    pub ap: u64,
+    /// AP needed for current level
+    #[serde(default, deserialize_with = "deserialize_number_opt")]
+    pub min_ap_for_current_level: Option<u64>,
+    /// AP needed for next level
+    #[serde(default, deserialize_with = "deserialize_number_opt")]
+    pub min_ap_for_next_level: Option<u64>,
+    /// agent current XM
+    #[serde(deserialize_with = "deserialize_number")]
+    pub energy: u32,
+    /// agent XM capacity
+    #[serde(deserialize_with = "deserialize_number")]
+    pub xm_capacity: u32,
+    /// agent available invites
+    #[serde(default, deserialize_with = "deserialize_number")]
+    pub available_invites: u32,
+}
+
+impl Player {
+    /// extracts `PLAYER` data from the Intel dashboard page
+    pub(crate) fn from_page(page: &str) -> Option<Self> {
+        let player = PLAYER.captures(page)?.get(1)?.as_str();
+        serde_json::from_str(player)
+            .map_err(|e| {
+                warn!("error deserializing PLAYER data: {}", e);
+            })
+            .ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn from_page() {
+        let page = r#"<script type="text/javascript">var PLAYER = {"ap": "47539381", "available_invites": 0, "energy": 22716, "level": 16, "min_ap_for_current_level": "40000000", "min_ap_for_next_level": "0", "nickname": "TerminateThis", "team": "RESISTANCE", "verified_level": 16, "xm_capacity": "22800"};</script>"#;
+        let player = super::Player::from_page(page).unwrap();
+        assert_eq!(player.nickname, "TerminateThis");
+        assert!(player.team.is_resistance());
+        assert_eq!(player.level, 16);
+        assert_eq!(player.ap, 47539381);
+        assert_eq!(player.xm_capacity, 22800);
+        assert_eq!(player.min_ap_for_next_level, Some(0));
+    }
+}
@@ -1,13 +1,21 @@
-use serde::{Deserialize, Serialize};
+use serde::{
+    Deserialize, Serialize,
+    de::{IntoDeserializer, value},
+};
 use smol_str::SmolStr;
 
+use crate::entities::Faction;
+
 /// intermediate plext construct
 pub type IntelPlext = (SmolStr, i64, PlextWrapper);
-/// intermediate markup construct
-pub type Markup = (SmolStr, MarkupElement);
+
+/// faction COMM category bit
+const CATEGORY_SECURE: u8 = 2;
+/// alerts COMM category bit
+const CATEGORY_ALERT: u8 = 4;
 
 /// COMM Tabs
-#[derive(Debug, Serialize)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Tab {
     /// COMM All Tabs
@@ -18,57 +26,273 @@ pub enum Tab {
     Alerts,
 }
 
+/// outgoing COMM message
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Message(String);
+
+impl Message {
+    /// creates an empty message
+    pub fn new() -> Self {
+        Message::default()
+    }
+
+    /// appends plain text
+    pub fn text(mut self, text: &str) -> Self {
+        self.0.push_str(text);
+        self
+    }
+
+    /// appends an agent mention, Intel turns it into an at player markup
+    pub fn mention(mut self, nickname: &str) -> Self {
+        // mentions must be a word on their own
+        if self.0.chars().last().is_some_and(|c| !c.is_whitespace()) {
+            self.0.push(' ');
+        }
+        self.0.push('@');
+        self.0.push_str(nickname.trim_start_matches('@'));
+        self
+    }
+
+    /// message text
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&str> for Message {
+    fn from(text: &str) -> Self {
+        Message(text.to_owned())
+    }
+}
+
+impl From<String> for Message {
+    fn from(text: String) -> Self {
+        Message(text)
+    }
+}
+
 /// endpoint reponse root
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct IntelResponse {
     /// "result" node
     pub result: Vec<IntelPlext>,
 }
 
 /// plext wrapper
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct PlextWrapper {
     /// plext
     pub plext: Plext,
 }
 
 /// plext core object
-#[derive(Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize)]
 pub struct Plext {
     /// text description
     pub text: SmolStr,
     /// team
-    pub team: SmolStr,
+    pub team: Team,
     /// markup
     pub markup: Vec<Markup>,
     /// type
     #[serde(rename = "plextType")]
-    pub plext_type: SmolStr,
+    pub plext_type: PlextType,
     /// category
     pub categories: u8,
 }
 
+impl Plext {
+    /// tab the message belongs to
+    pub fn tab(&self) -> Tab {
+        if self.categories & CATEGORY_ALERT != 0 {
+            Tab::Alerts
+        } else if self.categories & CATEGORY_SECURE != 0 {
+            Tab::Faction
+        } else {
+            Tab::All
+        }
+    }
+
+    /// checks if the message is a faction one
+    pub fn is_secure(&self) -> bool {
+        self.categories & CATEGORY_SECURE != 0 || self.markup.iter().any(|m| matches!(m, Markup::SecureFlag { .. }))
+    }
+}
+
+/// message or markup team, keeping the ones this crate doesn't know about
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "SmolStr")]
+pub enum Team {
+    /// known faction
+    Faction(Faction),
+    /// unknown team, as sent by Intel
+    Unknown(SmolStr),
+}
+
+impl Team {
+    /// faction, if known
+    pub fn faction(&self) -> Option<Faction> {
+        match self {
+            Team::Faction(faction) => Some(*faction),
+            Team::Unknown(_) => None,
+        }
+    }
+}
+
+impl From<SmolStr> for Team {
+    fn from(team: SmolStr) -> Self {
+        parse_faction(&team).map_or(Team::Unknown(team), Team::Faction)
+    }
+}
+
+// same names and aliases as Faction deserialization
+pub(crate) fn parse_faction(team: &str) -> Option<Faction> {
+    Faction::deserialize(IntoDeserializer::<value::Error>::into_deserializer(team)).ok()
+}
+
+/// plext types
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+pub enum PlextType {
+    /// public game event
+    #[serde(rename = "SYSTEM_BROADCAST")]
+    SystemBroadcast,
+    /// game event addressed to the current agent
+    #[serde(rename = "SYSTEM_NARROWCAST")]
+    SystemNarrowcast,
+    /// chat message
+    #[serde(rename = "PLAYER_GENERATED")]
+    PlayerGenerated,
+    /// unknown type
+    #[serde(other)]
+    Other,
+}
+
 /// markup object
-#[derive(Debug, Deserialize)]
-pub struct MarkupElement {
+#[derive(Clone, Debug, PartialEq)]
+pub enum Markup {
+    /// agent
+    Player {
+        /// nickname
+        plain: SmolStr,
+        /// faction, unknown when missing
+        team: Team,
+    },
+    /// portal
+    Portal {
+        /// title and address
+        plain: SmolStr,
+        /// title
+        name: SmolStr,
+        /// address
+        address: SmolStr,
+        /// latitude
+        lat: f64,
+        /// longitude
+        lng: f64,
+        /// faction, unknown when missing
+        team: Team,
+    },
+    /// plain text
+    Text {
+        /// text contents
+        plain: SmolStr,
+    },
+    /// faction name
+    Faction {
+        /// faction display name
+        plain: SmolStr,
+        /// faction, unknown when missing
+        team: Team,
+    },
+    /// chat message sender
+    Sender {
+        /// nickname followed by a colon
+        plain: SmolStr,
+        /// faction, unknown when missing
+        team: Team,
+    },
+    /// agent mentioned in a chat message
+    AtPlayer {
+        /// nickname preceded by an at sign
+        plain: SmolStr,
+        /// faction, unknown when missing
+        team: Team,
+    },
+    /// faction chat marker
+    SecureFlag {
+        /// text contents
+        plain: SmolStr,
+    },
+    /// unknown markup
+    Unknown {
+        /// markup tag
+        kind: SmolStr,
+        /// text contents
+        plain: SmolStr,
+    },
+}
+
+impl Markup {
     /// text contents
-    pub plain: SmolStr,
-    /// name
-    pub name: Option<SmolStr>,
-    /// address
-    pub address: Option<SmolStr>,
-    /// latitude
+    pub fn plain(&self) -> &SmolStr {
+        match self {
+            Markup::Player { plain, .. }
+            | Markup::Portal { plain, .. }
+            | Markup::Text { plain }
+            | Markup::Faction { plain, .. }
+            | Markup::Sender { plain, .. }
+            | Markup::AtPlayer { plain, .. }
+            | Markup::SecureFlag { plain }
+            | Markup::Unknown { plain, .. } => plain,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct MarkupElement {
+    plain: SmolStr,
+    name: Option<SmolStr>,
+    address: Option<SmolStr>,
     #[serde(rename = "latE6", deserialize_with = "crate::utils::deserialize_coord_opt", default)]
-    pub lat_e6: Option<f64>,
-    /// longitude
+    lat_e6: Option<f64>,
     #[serde(rename = "lngE6", deserialize_with = "crate::utils::deserialize_coord_opt", default)]
-    pub lng_e6: Option<f64>,
-    /// team
-    pub team: Option<SmolStr>,
+    lng_e6: Option<f64>,
+    #[serde(default)]
+    team: Option<SmolStr>,
+}
+
+impl<'de> Deserialize<'de> for Markup {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (kind, element) = <(SmolStr, MarkupElement)>::deserialize(deserializer)?;
+        let MarkupElement { plain, name, address, lat_e6, lng_e6, team } = element;
+        let team = Team::from(team.unwrap_or_default());
+        Ok(match kind.as_str() {
+            "PLAYER" => Markup::Player { plain, team },
+            "PORTAL" => match (lat_e6, lng_e6) {
+                (Some(lat), Some(lng)) => Markup::Portal {
+                    name: name.unwrap_or_else(|| plain.clone()),
+                    address: address.unwrap_or_default(),
+                    plain,
+                    lat,
+                    lng,
+                    team,
+                },
+                _ => Markup::Unknown { kind, plain },
+            },
+            "TEXT" => Markup::Text { plain },
+            "FACTION" => Markup::Faction { plain, team },
+            "SENDER" => Markup::Sender { plain, team },
+            "AT_PLAYER" => Markup::AtPlayer { plain, team },
+            "SECURE" => Markup::SecureFlag { plain },
+            _ => Markup::Unknown { kind, plain },
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::{comm::CommEvent, entities::Faction};
+
     #[test]
     fn plexts() {
         let res: super::IntelResponse = serde_json::from_str(r#"{"result":[["950163b7358548ff8d0f642a3d4876a6.d",1719762782388,{"plext":{"text":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301 linked A.M.G. Judo Murano (Fondamenta Antonio Colleoni, 14, 30141 Venezia VE, Italy) to Poste Italiane Murano LY (Fondamenta Antonio Maschio, 47-48, 30141 Venezia VE, Italy)","team":"NEUTRAL","markup":[["PLAYER",{"plain":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301","team":"NEUTRAL"}],["TEXT",{"plain":" linked "}],["PORTAL",{"plain":"A.M.G. Judo Murano (Fondamenta Antonio Colleoni, 14, 30141 Venezia VE, Italy)","name":"A.M.G. Judo Murano","address":"Fondamenta Antonio Colleoni, 14, 30141 Venezia VE, Italy","latE6":45455210,"lngE6":12354014,"team":"NEUTRAL"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Poste Italiane Murano LY (Fondamenta Antonio Maschio, 47-48, 30141 Venezia VE, Italy)","name":"Poste Italiane Murano LY","address":"Fondamenta Antonio Maschio, 47-48, 30141 Venezia VE, Italy","latE6":45455478,"lngE6":12356645,"team":"NEUTRAL"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["37b2d529f7de4ba6a03631fe9618f902.d",1719762782388,{"plext":{"text":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301 linked A.M.G. Judo Murano (Fondamenta Antonio Colleoni, 14, 30141 Venezia VE, Italy) to Leone alato S.Marco (Fondamenta Andrea Navagero, 59, 30141 Venezia VE, Italy)","team":"NEUTRAL","markup":[["PLAYER",{"plain":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301","team":"NEUTRAL"}],["TEXT",{"plain":" linked "}],["PORTAL",{"plain":"A.M.G. Judo Murano (Fondamenta Antonio Colleoni, 14, 30141 Venezia VE, Italy)","name":"A.M.G. Judo Murano","address":"Fondamenta Antonio Colleoni, 14, 30141 Venezia VE, Italy","latE6":45455210,"lngE6":12354014,"team":"NEUTRAL"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Leone alato S.Marco (Fondamenta Andrea Navagero, 59, 30141 Venezia VE, Italy)","name":"Leone alato S.Marco","address":"Fondamenta Andrea Navagero, 59, 30141 Venezia VE, Italy","latE6":45454742,"lngE6":12356855,"team":"NEUTRAL"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["bc4afa30553e4323a88dba7dffdc8995.d",1719762532642,{"plext":{"text":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301 linked Parco Giochi Delle Piscine (Vicolo Giacomo Zanella, 67/A, 31100 Treviso TV, Italy) to Treviso - Fontanella con drago (Via Castello d'Amore, 40, 31100 Treviso TV, Italy)","team":"NEUTRAL","markup":[["PLAYER",{"plain":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301","team":"NEUTRAL"}],["TEXT",{"plain":" linked "}],["PORTAL",{"plain":"Parco Giochi Delle Piscine (Vicolo Giacomo Zanella, 67/A, 31100 Treviso TV, Italy)","name":"Parco Giochi Delle Piscine","address":"Vicolo Giacomo Zanella, 67/A, 31100 Treviso TV, Italy","latE6":45670559,"lngE6":12268073,"team":"NEUTRAL"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Treviso - Fontanella con drago (Via Castello d'Amore, 40, 31100 Treviso TV, Italy)","name":"Treviso - Fontanella con drago","address":"Via Castello d'Amore, 40, 31100 Treviso TV, Italy","latE6":45673175,"lngE6":12257950,"team":"NEUTRAL"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["96692956466f413c91653f9d5c5ce1bb.d",1719762532642,{"plext":{"text":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301 linked Parco Giochi Delle Piscine (Vicolo Giacomo Zanella, 67/A, 31100 Treviso TV, Italy) to Selvana - Campo da Calcio (Via Giacomo Zannella, 7B, 31100 Treviso TV, Italy)","team":"NEUTRAL","markup":[["PLAYER",{"plain":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301","team":"NEUTRAL"}],["TEXT",{"plain":" linked "}],["PORTAL",{"plain":"Parco Giochi Delle Piscine (Vicolo Giacomo Zanella, 67/A, 31100 Treviso TV, Italy)","name":"Parco Giochi Delle Piscine","address":"Vicolo Giacomo Zanella, 67/A, 31100 Treviso TV, Italy","latE6":45670559,"lngE6":12268073,"team":"NEUTRAL"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Selvana - Campo da Calcio (Via Giacomo Zannella, 7B, 31100 Treviso TV, Italy)","name":"Selvana - Campo da Calcio","address":"Via Giacomo Zannella, 7B, 31100 Treviso TV, Italy","latE6":45675267,"lngE6":12263910,"team":"NEUTRAL"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["80272bf3f8eb4f62af02d73e02a7d9ab.d",1719762434058,{"plext":{"text":"Resistance agent btgalpi linked from Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy) to Campo da Basket (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Campo da Basket (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da Basket","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45386327,"lngE6":11799340,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["23b803c4335b4400a3032e7d2022596c.d",1719762434058,{"plext":{"text":"Resistance agent btgalpi created a Control Field @Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy) +1 MUs","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" created a Control Field @"}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"RESISTANCE"}],["TEXT",{"plain":" +"}],["TEXT",{"plain":"1"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["5631c96ab7554006a5908ba9dccd8f77.d",1719762432825,{"plext":{"text":"Resistance agent btgalpi linked from Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy) to Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Icona votiva in metalo","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385771,"lngE6":11798939,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["f925d55927294293a9f9a84c942e9b8f.d",1719762409260,{"plext":{"text":"btgalpi deployed a Resonator on Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" deployed a Resonator on "}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["438df9c950ec4a13b527ab440f516e25.d",1719762409260,{"plext":{"text":"btgalpi captured Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" captured "}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["acdfc2b77d2a4442b9bf037858471bc6.d",1719762372528,{"plext":{"text":"guerrafix deployed a Resonator on La tradotta. segnale con avviso accoppiato (Via Schiavonesca Vecchia, 67B, 31040 Volpago del Montello TV, Italy)","team":"ENLIGHTENED","markup":[["PLAYER",{"plain":"guerrafix","team":"ENLIGHTENED"}],["TEXT",{"plain":" deployed a Resonator on "}],["PORTAL",{"plain":"La tradotta. segnale con avviso accoppiato (Via Schiavonesca Vecchia, 67B, 31040 Volpago del Montello TV, Italy)","name":"La tradotta. segnale con avviso accoppiato","address":"Via Schiavonesca Vecchia, 67B, 31040 Volpago del Montello TV, Italy","latE6":45777523,"lngE6":12141673,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["3aaad33573f54a849c8c4a4814a9c2e8.d",1719762372528,{"plext":{"text":"guerrafix captured La tradotta. segnale con avviso accoppiato (Via Schiavonesca Vecchia, 67B, 31040 Volpago del Montello TV, Italy)","team":"ENLIGHTENED","markup":[["PLAYER",{"plain":"guerrafix","team":"ENLIGHTENED"}],["TEXT",{"plain":" captured "}],["PORTAL",{"plain":"La tradotta. segnale con avviso accoppiato (Via Schiavonesca Vecchia, 67B, 31040 Volpago del Montello TV, Italy)","name":"La tradotta. segnale con avviso accoppiato","address":"Via Schiavonesca Vecchia, 67B, 31040 Volpago del Montello TV, Italy","latE6":45777523,"lngE6":12141673,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["6302ce95eb15430390357682ce3e1413.d",1719762369998,{"plext":{"text":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301 linked Fontana Perpetua (Via Citolo da Perugia, 1, 35138 Padova PD, Italy) to Serbatoio dell'acquedotto (Viale della Rotonda, 35138 Padua, Italy)","team":"NEUTRAL","markup":[["PLAYER",{"plain":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301","team":"NEUTRAL"}],["TEXT",{"plain":" linked "}],["PORTAL",{"plain":"Fontana Perpetua (Via Citolo da Perugia, 1, 35138 Padova PD, Italy)","name":"Fontana Perpetua","address":"Via Citolo da Perugia, 1, 35138 Padova PD, Italy","latE6":45416486,"lngE6":11875661,"team":"NEUTRAL"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Serbatoio dell'acquedotto (Viale della Rotonda, 35138 Padua, Italy)","name":"Serbatoio dell'acquedotto","address":"Viale della Rotonda, 35138 Padua, Italy","latE6":45416235,"lngE6":11875290,"team":"NEUTRAL"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["8779d61a6a864989945d2feac0a9d192.d",1719762355092,{"plext":{"text":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301 linked Breda di Piave - Murales giochi estivi (Via Roma, 8, 31030 Breda di Piave TV, Italy) to Ufficio Postale di Breda di Piave (N,, Piazza Italia, 13, 31030 Breda di Piave TV, Italy)","team":"NEUTRAL","markup":[["PLAYER",{"plain":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301","team":"NEUTRAL"}],["TEXT",{"plain":" linked "}],["PORTAL",{"plain":"Breda di Piave - Murales giochi estivi (Via Roma, 8, 31030 Breda di Piave TV, Italy)","name":"Breda di Piave - Murales giochi estivi","address":"Via Roma, 8, 31030 Breda di Piave TV, Italy","latE6":45720428,"lngE6":12329917,"team":"NEUTRAL"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Ufficio Postale di Breda di Piave (N,, Piazza Italia, 13, 31030 Breda di Piave TV, Italy)","name":"Ufficio Postale di Breda di Piave","address":"N,, Piazza Italia, 13, 31030 Breda di Piave TV, Italy","latE6":45720827,"lngE6":12332374,"team":"NEUTRAL"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["4e1a7fd446f6408399472fb0cb0a74fd.d",1719762338577,{"plext":{"text":"Resistance agent btgalpi linked from Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy) to Campo da Basket (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Icona votiva in metalo","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385771,"lngE6":11798939,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Campo da Basket (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da Basket","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45386327,"lngE6":11799340,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["43628d2ed1f94579969d57af8b655686.d",1719762338124,{"plext":{"text":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301 linked Murale Boogie - Orion (Corso Milano, 177, 35139 Padova PD, Italy) to Jump Street art (Via Digione, 3, 35138 Padova PD, Italy)","team":"NEUTRAL","markup":[["PLAYER",{"plain":"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301","team":"NEUTRAL"}],["TEXT",{"plain":" linked "}],["PORTAL",{"plain":"Murale Boogie - Orion (Corso Milano, 177, 35139 Padova PD, Italy)","name":"Murale Boogie - Orion","address":"Corso Milano, 177, 35139 Padova PD, Italy","latE6":45411193,"lngE6":11866572,"team":"NEUTRAL"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Jump Street art (Via Digione, 3, 35138 Padova PD, Italy)","name":"Jump Street art","address":"Via Digione, 3, 35138 Padova PD, Italy","latE6":45411968,"lngE6":11861669,"team":"NEUTRAL"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["ae5e911efa404c64a62089dbc865a959.d",1719762323258,{"plext":{"text":"btgalpi destroyed a Resonator on Bassorilievo Madonna di Fatima con crocifisso (Via S. Domenico, 212, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed a Resonator on "}],["PORTAL",{"plain":"Bassorilievo Madonna di Fatima con crocifisso (Via S. Domenico, 212, 35030 Selvazzano Dentro PD, Italy)","name":"Bassorilievo Madonna di Fatima con crocifisso","address":"Via S. Domenico, 212, 35030 Selvazzano Dentro PD, Italy","latE6":45385465,"lngE6":11799183,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["ee7a3dae4ce24c909fe9997db9439561.d",1719762321362,{"plext":{"text":"Agent btgalpi destroyed the Enlightened Link Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy) to Capitello S. Lorenzo (Via San Lorenzo, 29, 35031 Abano Terme Padua, Italy)","team":"ENLIGHTENED","markup":[["TEXT",{"plain":"Agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed the "}],["FACTION",{"team":"ENLIGHTENED","plain":"Enlightened"}],["TEXT",{"plain":" Link "}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"ENLIGHTENED"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Capitello S. Lorenzo (Via San Lorenzo, 29, 35031 Abano Terme Padua, Italy)","name":"Capitello S. Lorenzo","address":"Via San Lorenzo, 29, 35031 Abano Terme Padua, Italy","latE6":45372064,"lngE6":11798754,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["d759f198aaa942a6a66a69171aa60334.d",1719762321362,{"plext":{"text":"Agent btgalpi destroyed the Enlightened Link Bassorilievo Madonna di Fatima con crocifisso (Via S. Domenico, 212, 35030 Selvazzano Dentro PD, Italy) to Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"ENLIGHTENED","markup":[["TEXT",{"plain":"Agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed the "}],["FACTION",{"team":"ENLIGHTENED","plain":"Enlightened"}],["TEXT",{"plain":" Link "}],["PORTAL",{"plain":"Bassorilievo Madonna di Fatima con crocifisso (Via S. Domenico, 212, 35030 Selvazzano Dentro PD, Italy)","name":"Bassorilievo Madonna di Fatima con crocifisso","address":"Via S. Domenico, 212, 35030 Selvazzano Dentro PD, Italy","latE6":45385465,"lngE6":11799183,"team":"ENLIGHTENED"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["c871d0ce7a224d91bf37e75b076f0e8b.d",1719762321362,{"plext":{"text":"Agent btgalpi destroyed the Enlightened Link Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy) to Parco Comunale (Via Piemonte, 11, 35030 Selvazzano Dentro PD, Italy)","team":"ENLIGHTENED","markup":[["TEXT",{"plain":"Agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed the "}],["FACTION",{"team":"ENLIGHTENED","plain":"Enlightened"}],["TEXT",{"plain":" Link "}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"ENLIGHTENED"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Parco Comunale (Via Piemonte, 11, 35030 Selvazzano Dentro PD, Italy)","name":"Parco Comunale","address":"Via Piemonte, 11, 35030 Selvazzano Dentro PD, Italy","latE6":45389606,"lngE6":11787943,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["bf9a4a7ba8ab4dbfb23f47ecae0fca5c.d",1719762321362,{"plext":{"text":"Agent btgalpi destroyed the Enlightened Control Field @Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy) -7 MUs","team":"RESISTANCE","markup":[["TEXT",{"plain":"Agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed the "}],["FACTION",{"team":"ENLIGHTENED","plain":"Enlightened"}],["TEXT",{"plain":" Control Field @"}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"ENLIGHTENED"}],["TEXT",{"plain":" -"}],["TEXT",{"plain":"7"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["9359bf691719440daa4781e920c90f77.d",1719762321362,{"plext":{"text":"Agent btgalpi destroyed the Enlightened Control Field @Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy) -413 MUs","team":"RESISTANCE","markup":[["TEXT",{"plain":"Agent "}],["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed the "}],["FACTION",{"team":"ENLIGHTENED","plain":"Enlightened"}],["TEXT",{"plain":" Control Field @"}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"ENLIGHTENED"}],["TEXT",{"plain":" -"}],["TEXT",{"plain":"413"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["e059c927336248b8a2fb6f4704981646.d",1719762298866,{"plext":{"text":"btgalpi destroyed a Resonator on Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed a Resonator on "}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["f2f6df3cfb8e445ba43f5498124e3483.d",1719762290341,{"plext":{"text":"guerrafix deployed a Resonator on La tradotta. 12 (Via Lavaio Basso, 50, 31040 Volpago del Montello TV, Italy)","team":"ENLIGHTENED","markup":[["PLAYER",{"plain":"guerrafix","team":"ENLIGHTENED"}],["TEXT",{"plain":" deployed a Resonator on "}],["PORTAL",{"plain":"La tradotta. 12 (Via Lavaio Basso, 50, 31040 Volpago del Montello TV, Italy)","name":"La tradotta. 12","address":"Via Lavaio Basso, 50, 31040 Volpago del Montello TV, Italy","latE6":45776674,"lngE6":12139036,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["2e78ba95d82c437bb217b598bf29f07a.d",1719762290341,{"plext":{"text":"guerrafix captured La tradotta. 12 (Via Lavaio Basso, 50, 31040 Volpago del Montello TV, Italy)","team":"ENLIGHTENED","markup":[["PLAYER",{"plain":"guerrafix","team":"ENLIGHTENED"}],["TEXT",{"plain":" captured "}],["PORTAL",{"plain":"La tradotta. 12 (Via Lavaio Basso, 50, 31040 Volpago del Montello TV, Italy)","name":"La tradotta. 12","address":"Via Lavaio Basso, 50, 31040 Volpago del Montello TV, Italy","latE6":45776674,"lngE6":12139036,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["833e12d2601545aaa89aaae80273d621.d",1719762282386,{"plext":{"text":"btgalpi destroyed a Resonator on Chiesa San Domenico (Via San Giuseppe, 48, 35030 Selvazzano Dentro Province of Padua, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed a Resonator on "}],["PORTAL",{"plain":"Chiesa San Domenico (Via San Giuseppe, 48, 35030 Selvazzano Dentro Province of Padua, Italy)","name":"Chiesa San Domenico","address":"Via San Giuseppe, 48, 35030 Selvazzano Dentro Province of Padua, Italy","latE6":45386584,"lngE6":11798832,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["01cad042ff5449eda474a076a9761182.d",1719762277847,{"plext":{"text":"btgalpi destroyed a Resonator on Parco Perlasca (Via S. Domenico, 23, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed a Resonator on "}],["PORTAL",{"plain":"Parco Perlasca (Via S. Domenico, 23, 35030 Selvazzano Dentro PD, Italy)","name":"Parco Perlasca","address":"Via S. Domenico, 23, 35030 Selvazzano Dentro PD, Italy","latE6":45385408,"lngE6":11798846,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["ba1c258b18904ad7b6cdcc7586d9fbd0.d",1719762258921,{"plext":{"text":"Resistance agent CeccoMan linked from Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy) to Palazzo Papafava dei Carraresi (Via Cesare Battisti, 3, 35121 Padua, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","name":"Fontana di Piazza delle Erbe","address":"Via Municipio, 35122 Padua, Province of Padua, Italy","latE6":45406794,"lngE6":11875778,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Palazzo Papafava dei Carraresi (Via Cesare Battisti, 3, 35121 Padua, Italy)","name":"Palazzo Papafava dei Carraresi","address":"Via Cesare Battisti, 3, 35121 Padua, Italy","latE6":45407108,"lngE6":11877477,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["70a651da94c74221bf7a759219febb7e.d",1719762258921,{"plext":{"text":"Resistance agent CeccoMan created a Control Field @Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy) +12 MUs","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" created a Control Field @"}],["PORTAL",{"plain":"Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","name":"Fontana di Piazza delle Erbe","address":"Via Municipio, 35122 Padua, Province of Padua, Italy","latE6":45406794,"lngE6":11875778,"team":"RESISTANCE"}],["TEXT",{"plain":" +"}],["TEXT",{"plain":"12"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["1deb07fa8b7c45beaa5b3298f09a310f.d",1719762258921,{"plext":{"text":"Resistance agent CeccoMan created a Control Field @Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy) +1 MUs","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" created a Control Field @"}],["PORTAL",{"plain":"Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","name":"Fontana di Piazza delle Erbe","address":"Via Municipio, 35122 Padua, Province of Padua, Italy","latE6":45406794,"lngE6":11875778,"team":"RESISTANCE"}],["TEXT",{"plain":" +"}],["TEXT",{"plain":"1"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["9e306bba67d94972bd188acd37873735.d",1719762257090,{"plext":{"text":"Resistance agent CeccoMan created a Control Field @Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy) +2 MUs","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" created a Control Field @"}],["PORTAL",{"plain":"Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","name":"Fontana di Piazza delle Erbe","address":"Via Municipio, 35122 Padua, Province of Padua, Italy","latE6":45406794,"lngE6":11875778,"team":"RESISTANCE"}],["TEXT",{"plain":" +"}],["TEXT",{"plain":"2"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["3e64124e0b8b4629b7cc6333fba6aa46.d",1719762257090,{"plext":{"text":"Resistance agent CeccoMan linked from Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy) to Street Art Kitty (Via Pietro D'Abano, 35139 Padua, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","name":"Fontana di Piazza delle Erbe","address":"Via Municipio, 35122 Padua, Province of Padua, Italy","latE6":45406794,"lngE6":11875778,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Street Art Kitty (Via Pietro D'Abano, 35139 Padua, Italy)","name":"Street Art Kitty","address":"Via Pietro D'Abano, 35139 Padua, Italy","latE6":45407846,"lngE6":11875054,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["9906e832524a43a5a7094cfe78b6f86e.d",1719762203796,{"plext":{"text":"Resistance agent CeccoMan linked from Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy) to Palazzo Papafava dei Carraresi (Via Cesare Battisti, 3, 35121 Padua, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","name":"Generale tedesco","address":"Via dei Fabbri, 18, 35122 Padua, Italy","latE6":45406674,"lngE6":11875542,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Palazzo Papafava dei Carraresi (Via Cesare Battisti, 3, 35121 Padua, Italy)","name":"Palazzo Papafava dei Carraresi","address":"Via Cesare Battisti, 3, 35121 Padua, Italy","latE6":45407108,"lngE6":11877477,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["2787164c6a374323a18f0da611c0f68d.d",1719762203796,{"plext":{"text":"Resistance agent CeccoMan created a Control Field @Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy) +15 MUs","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" created a Control Field @"}],["PORTAL",{"plain":"Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","name":"Generale tedesco","address":"Via dei Fabbri, 18, 35122 Padua, Italy","latE6":45406674,"lngE6":11875542,"team":"RESISTANCE"}],["TEXT",{"plain":" +"}],["TEXT",{"plain":"15"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["73455a2c72474aeca607fb264315f4e0.d",1719762201732,{"plext":{"text":"Resistance agent CeccoMan linked from Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy) to Street Art Kitty (Via Pietro D'Abano, 35139 Padua, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","name":"Generale tedesco","address":"Via dei Fabbri, 18, 35122 Padua, Italy","latE6":45406674,"lngE6":11875542,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Street Art Kitty (Via Pietro D'Abano, 35139 Padua, Italy)","name":"Street Art Kitty","address":"Via Pietro D'Abano, 35139 Padua, Italy","latE6":45407846,"lngE6":11875054,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["4eb7b77d95134ab19ae6d234b69d3840.d",1719762201732,{"plext":{"text":"Resistance agent CeccoMan created a Control Field @Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy) +3 MUs","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" created a Control Field @"}],["PORTAL",{"plain":"Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","name":"Generale tedesco","address":"Via dei Fabbri, 18, 35122 Padua, Italy","latE6":45406674,"lngE6":11875542,"team":"RESISTANCE"}],["TEXT",{"plain":" +"}],["TEXT",{"plain":"3"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["564bf844b367473ca7529deaee1d6703.d",1719762199621,{"plext":{"text":"Resistance agent CeccoMan linked from Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy) to Bassorilievo con draghi (Piazza della Frutta, 35122 Padua, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","name":"Generale tedesco","address":"Via dei Fabbri, 18, 35122 Padua, Italy","latE6":45406674,"lngE6":11875542,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Bassorilievo con draghi (Piazza della Frutta, 35122 Padua, Italy)","name":"Bassorilievo con draghi","address":"Piazza della Frutta, 35122 Padua, Italy","latE6":45407338,"lngE6":11874792,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["4e113d173c904a8e9589eaf840858c20.d",1719762196591,{"plext":{"text":"Resistance agent CeccoMan linked from Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy) to Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","name":"Generale tedesco","address":"Via dei Fabbri, 18, 35122 Padua, Italy","latE6":45406674,"lngE6":11875542,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","name":"Fontana di Piazza delle Erbe","address":"Via Municipio, 35122 Padua, Province of Padua, Italy","latE6":45406794,"lngE6":11875778,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["e1e6cb2464c44bf3987a31913d6c85a6.d",1719762191686,{"plext":{"text":"btgalpi deployed a Resonator on Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" deployed a Resonator on "}],["PORTAL",{"plain":"Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Icona votiva in metalo","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385771,"lngE6":11798939,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["2b695e2a39224b81b9d775ce62cdead4.d",1719762191686,{"plext":{"text":"btgalpi captured Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" captured "}],["PORTAL",{"plain":"Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Icona votiva in metalo","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385771,"lngE6":11798939,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["fb5eb96964df4cad82d7428d78bb3118.d",1719762186104,{"plext":{"text":"CeccoMan deployed a Resonator on Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" deployed a Resonator on "}],["PORTAL",{"plain":"Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","name":"Generale tedesco","address":"Via dei Fabbri, 18, 35122 Padua, Italy","latE6":45406674,"lngE6":11875542,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["64f1abe7a6cd46389e3c57c0920ca46e.d",1719762186104,{"plext":{"text":"CeccoMan captured Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" captured "}],["PORTAL",{"plain":"Generale tedesco (Via dei Fabbri, 18, 35122 Padua, Italy)","name":"Generale tedesco","address":"Via dei Fabbri, 18, 35122 Padua, Italy","latE6":45406674,"lngE6":11875542,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["5fa642c8c2af460a87773b281b3f398c.d",1719762182507,{"plext":{"text":"Resistance agent Z0FK4 linked from Targa gemellaggio (Via Garibaldi, 8, 33040 Pradamano UD, Italy) to La mucca (Via Lovaria, 48, 33050 Pavia di Udine UD, Italy)","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"Z0FK4","team":"RESISTANCE"}],["TEXT",{"plain":" linked from "}],["PORTAL",{"plain":"Targa gemellaggio (Via Garibaldi, 8, 33040 Pradamano UD, Italy)","name":"Targa gemellaggio","address":"Via Garibaldi, 8, 33040 Pradamano UD, Italy","latE6":46033355,"lngE6":13306374,"team":"RESISTANCE"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"La mucca (Via Lovaria, 48, 33050 Pavia di Udine UD, Italy)","name":"La mucca","address":"Via Lovaria, 48, 33050 Pavia di Udine UD, Italy","latE6":46003295,"lngE6":13303723,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["13bf9a242dc940dc874b5f41faab748c.d",1719762182507,{"plext":{"text":"Resistance agent Z0FK4 created a Control Field @Targa gemellaggio (Via Garibaldi, 8, 33040 Pradamano UD, Italy) +449 MUs","team":"RESISTANCE","markup":[["FACTION",{"team":"RESISTANCE","plain":"Resistance"}],["TEXT",{"plain":" agent "}],["PLAYER",{"plain":"Z0FK4","team":"RESISTANCE"}],["TEXT",{"plain":" created a Control Field @"}],["PORTAL",{"plain":"Targa gemellaggio (Via Garibaldi, 8, 33040 Pradamano UD, Italy)","name":"Targa gemellaggio","address":"Via Garibaldi, 8, 33040 Pradamano UD, Italy","latE6":46033355,"lngE6":13306374,"team":"RESISTANCE"}],["TEXT",{"plain":" +"}],["TEXT",{"plain":"449"}],["TEXT",{"plain":" MUs"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["dcaaaf2f4f43416da21ffd259f63a6c7.d",1719762128785,{"plext":{"text":"btgalpi destroyed a Resonator on Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed a Resonator on "}],["PORTAL",{"plain":"Campo da calcio in erba sintetica (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Campo da calcio in erba sintetica","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385714,"lngE6":11799382,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["34b025e1ae0a464d82a55eba9b03e81f.d",1719762127331,{"plext":{"text":"CeccoMan deployed a Resonator on Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" deployed a Resonator on "}],["PORTAL",{"plain":"Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","name":"Fontana di Piazza delle Erbe","address":"Via Municipio, 35122 Padua, Province of Padua, Italy","latE6":45406794,"lngE6":11875778,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["208316fa15274ce1bf1af61930050e3e.d",1719762127331,{"plext":{"text":"CeccoMan captured Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" captured "}],["PORTAL",{"plain":"Fontana di Piazza delle Erbe (Via Municipio, 35122 Padua, Province of Padua, Italy)","name":"Fontana di Piazza delle Erbe","address":"Via Municipio, 35122 Padua, Province of Padua, Italy","latE6":45406794,"lngE6":11875778,"team":"RESISTANCE"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["9c4b7887849b4c579acfb0eb743c9790.d",1719762126826,{"plext":{"text":"btgalpi destroyed a Resonator on Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"btgalpi","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed a Resonator on "}],["PORTAL",{"plain":"Icona votiva in metalo (Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy)","name":"Icona votiva in metalo","address":"Via S. Domenico, 12, 35030 Selvazzano Dentro PD, Italy","latE6":45385771,"lngE6":11798939,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["eeac74366fe344f691bedf4bd7ca460c.d",1719762110383,{"plext":{"text":"CeccoMan destroyed a Resonator on Il Corso (Riviera Tito Livio, 12, 35123 Padua, Province of Padua, Italy)","team":"RESISTANCE","markup":[["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed a Resonator on "}],["PORTAL",{"plain":"Il Corso (Riviera Tito Livio, 12, 35123 Padua, Province of Padua, Italy)","name":"Il Corso","address":"Riviera Tito Livio, 12, 35123 Padua, Province of Padua, Italy","latE6":45405879,"lngE6":11877112,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["7c0b3cc095fa42e88b79918e33a6550e.d",1719762105468,{"plext":{"text":"Agent CeccoMan destroyed the Enlightened Link Affresco (Via San Canziano, 2, 35122 Padua, Italy) to Chiesa Di San Canziano (Via delle Piazze, 1, 35122 Padova PD, Italy)","team":"ENLIGHTENED","markup":[["TEXT",{"plain":"Agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed the "}],["FACTION",{"team":"ENLIGHTENED","plain":"Enlightened"}],["TEXT",{"plain":" Link "}],["PORTAL",{"plain":"Affresco (Via San Canziano, 2, 35122 Padua, Italy)","name":"Affresco","address":"Via San Canziano, 2, 35122 Padua, Italy","latE6":45406483,"lngE6":11876685,"team":"ENLIGHTENED"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Chiesa Di San Canziano (Via delle Piazze, 1, 35122 Padova PD, Italy)","name":"Chiesa Di San Canziano","address":"Via delle Piazze, 1, 35122 Padova PD, Italy","latE6":45406307,"lngE6":11876288,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}],["6b8888e054014e7da32bc47fd8349ece.d",1719762105468,{"plext":{"text":"Agent CeccoMan destroyed the Neutral Link Affreschi - Leoni Marciani (Piazza Erbe, 16, 35122 Padova PD, Italy) to Colonna Papale (Piazza della Frutta, 35139 Padua, Padua, Italy)","team":"NEUTRAL","markup":[["TEXT",{"plain":"Agent "}],["PLAYER",{"plain":"CeccoMan","team":"RESISTANCE"}],["TEXT",{"plain":" destroyed the "}],["FACTION",{"team":"NEUTRAL","plain":"Neutral"}],["TEXT",{"plain":" Link "}],["PORTAL",{"plain":"Affreschi - Leoni Marciani (Piazza Erbe, 16, 35122 Padova PD, Italy)","name":"Affreschi - Leoni Marciani","address":"Piazza Erbe, 16, 35122 Padova PD, Italy","latE6":45406791,"lngE6":11874924,"team":"NEUTRAL"}],["TEXT",{"plain":" to "}],["PORTAL",{"plain":"Colonna Papale (Piazza della Frutta, 35139 Padua, Padua, Italy)","name":"Colonna Papale","address":"Piazza della Frutta, 35139 Padua, Padua, Italy","latE6":45407691,"lngE6":11875351,"team":"NEUTRAL"}]],"plextType":"SYSTEM_BROADCAST","categories":1}}]]}"#).unwrap();
@@ -76,11 +300,50 @@ mod tests {
             .result
             .into_iter()
             .flat_map(|(_id, _time, plexts)| {
-                plexts.plext.markup.into_iter().filter_map(|(t, markup)| (t == "TEXT").then_some(markup.plain))
+                plexts.plext.markup.into_iter().filter_map(|markup| match markup {
+                    super::Markup::Text { plain } => Some(plain),
+                    _ => None,
+                })
             })
             .collect::<Vec<_>>();
         values.sort_unstable();
         values.dedup();
         println!("{:#?}", values);
     }
+
+    #[test]
+    fn markup() {
+        let plext: super::Plext = serde_json::from_str(r#"{"text":"[secure] btgalpi: hi @CeccoMan","team":"RESISTANCE","markup":[["SECURE",{"plain":"[secure] "}],["SENDER",{"plain":"btgalpi: ","team":"RESISTANCE"}],["TEXT",{"plain":"hi "}],["AT_PLAYER",{"plain":"@CeccoMan","team":"RESISTANCE"}],["EMOJI",{"plain":":)"}]],"plextType":"PLAYER_GENERATED","categories":2}"#).unwrap();
+        assert_eq!(plext.plext_type, super::PlextType::PlayerGenerated);
+        assert_eq!(plext.tab(), super::Tab::Faction);
+        assert!(plext.is_secure());
+        assert_eq!(
+            plext.markup[1],
+            super::Markup::Sender { plain: "btgalpi: ".into(), team: super::Team::Faction(Faction::Resistance) }
+        );
+        assert_eq!(plext.markup[4], super::Markup::Unknown { kind: "EMOJI".into(), plain: ":)".into() });
+    }
+
+    #[test]
+    fn unknown_team() {
+        let plext: super::Plext = serde_json::from_str(r#"{"text":"Agent ___ captured Campo","team":"ALIENS","markup":[["TEXT",{"plain":"Agent "}],["PLAYER",{"plain":"___","team":"ALIENS"}],["TEXT",{"plain":" captured "}],["PORTAL",{"plain":"Campo","name":"Campo","address":"","latE6":45406674,"lngE6":11875542,"team":"ENLIGHTENED"}]],"plextType":"SYSTEM_BROADCAST","categories":1}"#).unwrap();
+        assert_eq!(plext.team, super::Team::Unknown("ALIENS".into()));
+        assert_eq!(plext.team.faction(), None);
+        assert_eq!(
+            plext.markup[1],
+            super::Markup::Player { plain: "___".into(), team: super::Team::Unknown("ALIENS".into()) }
+        );
+        assert!(matches!(
+            plext.markup[3],
+            super::Markup::Portal { team: super::Team::Faction(Faction::Enlightened), .. }
+        ));
+        assert!(matches!(plext.event(), CommEvent::PortalCaptured { player, portal }
+            if player.name == "___" && player.team.faction().is_none() && portal.name == "Campo"));
+    }
+
+    #[test]
+    fn message() {
+        let message = super::Message::new().text("meet").mention("@CeccoMan").text(" and ").mention("btgalpi");
+        assert_eq!(message.as_str(), "meet @CeccoMan and @btgalpi");
+    }
 }
@@ -0,0 +1,270 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use smol_str::SmolStr;
+use tokio::sync::Mutex;
+use tracing::warn;
+
+use crate::{
+    Error,
+    coords::BoundingBox,
+    plexts::{IntelPlext, Tab},
+};
+
+// consecutive failures after which a stream gives up
+const MAX_FAILURES: usize = 5;
+// getPlexts page size
+const PAGE_SIZE: usize = 50;
+
+#[derive(Default)]
+pub(crate) struct BackfillState {
+    max_timestamp: Option<i64>,
+    seen: HashSet<SmolStr>,
+    failures: usize,
+    done: bool,
+}
+
+pub(crate) struct Backfill<'a> {
+    pub(crate) inner: &'a super::Intel<'a>,
+    pub(crate) bbox: BoundingBox,
+    pub(crate) tab: Tab,
+    pub(crate) since: i64,
+    pub(crate) state: Mutex<BackfillState>,
+}
+
+impl Backfill<'_> {
+    /// next older page, `None` when history is exhausted or after the terminal error
+    pub(crate) async fn next_page(self: Arc<Self>) -> Option<Result<Vec<IntelPlext>, Error>> {
+        let mut state = self.state.lock().await;
+        if state.done {
+            return None;
+        }
+
+        let res = match self.inner.get_plexts(self.bbox, self.tab, Some(self.since), state.max_timestamp).await {
+            Ok(res) => res,
+            Err(e) => {
+                state.failures += 1;
+                if state.failures < MAX_FAILURES {
+                    warn!("COMM backfill failed, retrying: {}", e);
+                    return Some(Ok(Vec::new()));
+                }
+                warn!("giving up COMM backfill after {} failures", state.failures);
+                state.done = true;
+                return Some(Err(e));
+            }
+        };
+        state.failures = 0;
+
+        // requests start from `since`, so an empty page is the end of history
+        let oldest = res.result.iter().map(|(_, timestamp, _)| *timestamp).min()?;
+        // Intel can't page inside a single timestamp, so a full page sharing one can't be completed
+        let saturated =
+            res.result.len() >= PAGE_SIZE && res.result.iter().all(|(_, timestamp, _)| *timestamp == oldest);
+        let page = res
+            .result
+            .into_iter()
+            .filter(|(id, timestamp, _)| *timestamp >= self.since && state.seen.insert(id.clone()))
+            .collect::<Vec<_>>();
+        // pages overlap on the oldest timestamp, since messages sharing it could have been cut off,
+        // a page made only of already seen messages means there's nothing more at that timestamp
+        if page.is_empty() && saturated {
+            warn!("more than {} COMM messages at {}, some of them have been skipped", PAGE_SIZE, oldest);
+        }
+        state.max_timestamp = Some(if page.is_empty() { oldest - 1 } else { oldest });
+        Some(Ok(page))
+    }
+}
+
+pub(crate) struct TailState {
+    // starting point is exclusive, messages sharing it were yielded by the previous run
+    since: Option<i64>,
+    last_timestamp: Option<i64>,
+    // ids of the messages sharing the newest timestamp, which is requested again at every poll
+    seen: HashSet<SmolStr>,
+    interval: Duration,
+    started: bool,
+    polled: bool,
+    failures: usize,
+    done: bool,
+}
+
+impl TailState {
+    pub(crate) fn new(since: Option<i64>, interval: Duration) -> Self {
+        // without a starting point the first poll only sets the baseline
+        TailState {
+            since,
+            last_timestamp: since,
+            seen: HashSet::new(),
+            interval,
+            started: since.is_some(),
+            polled: false,
+            failures: 0,
+            done: false,
+        }
+    }
+}
+
+pub(crate) struct Tail<'a> {
+    pub(crate) inner: &'a super::Intel<'a>,
+    pub(crate) bbox: BoundingBox,
+    pub(crate) tab: Tab,
+    pub(crate) poll_interval: Duration,
+    pub(crate) state: Mutex<TailState>,
+}
+
+impl Tail<'_> {
+    /// new messages since the last poll, oldest first, `None` after the terminal error
+    pub(crate) async fn next_messages(self: Arc<Self>) -> Option<Result<Vec<IntelPlext>, Error>> {
+        let mut state = self.state.lock().await;
+        if state.done {
+            return None;
+        }
+        if state.polled {
+            tokio::time::sleep(state.interval).await;
+        }
+        state.polled = true;
+
+        let (min_interval, max_interval) = (self.poll_interval / 4, self.poll_interval * 4);
+        let mut messages = match self.poll(&state).await {
+            Ok(messages) => messages,
+            Err(e) => {
+                state.failures += 1;
+                if state.failures >= MAX_FAILURES {
+                    warn!("giving up COMM tail after {} failures", state.failures);
+                    state.done = true;
+                    return Some(Err(e));
+                }
+                warn!("COMM poll failed, backing off: {}", e);
+                state.interval = (state.interval * 2).min(max_interval);
+                return Some(Ok(Vec::new()));
+            }
+        };
+        state.failures = 0;
+        messages.sort_by_key(|(_, timestamp, _)| *timestamp);
+
+        // busy areas are polled faster, quiet ones slower
+        state.interval = if messages.is_empty() {
+            (state.interval * 3 / 2).min(max_interval)
+        } else {
+            (state.interval / 2).max(min_interval)
+        };
+
+        if let Some(newest) = messages.last().map(|(_, timestamp, _)| *timestamp) {
+            if state.last_timestamp != Some(newest) {
+                state.seen.clear();
+            }
+            state.seen.extend(messages.iter().filter(|(_, t, _)| *t == newest).map(|(id, _, _)| id.clone()));
+            state.last_timestamp = Some(newest);
+        }
+
+        if !state.started {
+            state.started = true;
+            return Some(Ok(Vec::new()));
+        }
+        Some(Ok(messages))
+    }
+
+    async fn poll(&self, state: &TailState) -> Result<Vec<IntelPlext>, Error> {
+        state
+            .poll(self.poll_interval / 4, |min_timestamp, max_timestamp| async move {
+                Ok(self.inner.get_plexts(self.bbox, self.tab, min_timestamp, max_timestamp).await?.result)
+            })
+            .await
+    }
+}
+
+impl TailState {
+    /// messages newer than the last seen ones, a full page makes it page back until them,
+    /// waiting `page_delay` between pages
+    async fn poll<F, Fut>(&self, page_delay: Duration, mut fetch: F) -> Result<Vec<IntelPlext>, Error>
+    where
+        F: FnMut(Option<i64>, Option<i64>) -> Fut,
+        Fut: Future<Output = Result<Vec<IntelPlext>, Error>>,
+    {
+        let mut messages = Vec::new();
+        let mut ids = HashSet::new();
+        let mut max_timestamp = None;
+        loop {
+            if max_timestamp.is_some() {
+                tokio::time::sleep(page_delay).await;
+            }
+            let page = fetch(self.last_timestamp, max_timestamp).await?;
+            let full = page.len() >= PAGE_SIZE;
+            let oldest = page.iter().map(|(_, timestamp, _)| *timestamp).min();
+            let before = messages.len();
+            messages.extend(page.into_iter().filter(|(id, timestamp, _)| {
+                self.since.is_none_or(|since| *timestamp > since) && !self.seen.contains(id) && ids.insert(id.clone())
+            }));
+
+            // the baseline only needs the newest page, there's nothing to catch up with
+            let Some(last_timestamp) = self.last_timestamp else {
+                break;
+            };
+            match oldest {
+                // pages overlap on the oldest timestamp, since messages sharing it could have been cut off,
+                // a page without new messages means there's nothing more at that timestamp
+                Some(oldest) if full => {
+                    let next = if messages.len() > before { oldest } else { oldest - 1 };
+                    if next < last_timestamp {
+                        break;
+                    }
+                    max_timestamp = Some(next);
+                }
+                _ => break,
+            }
+        }
+        Ok(messages)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, time::Duration};
+
+    use crate::plexts::IntelPlext;
+
+    fn message(id: usize, timestamp: i64) -> IntelPlext {
+        serde_json::from_value(serde_json::json!([
+            format!("{id}.d"),
+            timestamp,
+            {"plext": {"text": "hi", "team": "RESISTANCE", "markup": [], "plextType": "PLAYER_GENERATED", "categories": 1}}
+        ]))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn tail_catch_up() {
+        // 130 messages, two per millisecond, from 1000 to 1064
+        let history = (0..130).map(|i| message(i, 1000 + i as i64 / 2)).collect::<Vec<_>>();
+        // newest messages up to the page size, as getPlexts does
+        let fetch = |min: Option<i64>, max: Option<i64>| {
+            let mut page = history
+                .iter()
+                .filter(|(_, t, _)| min.is_none_or(|min| *t >= min) && max.is_none_or(|max| *t <= max))
+                .cloned()
+                .collect::<Vec<_>>();
+            page.sort_by_key(|(_, t, _)| -t);
+            page.truncate(super::PAGE_SIZE);
+            async move { Ok(page) }
+        };
+
+        // without a baseline a single page is requested
+        let mut state = super::TailState::new(None, Duration::ZERO);
+        let mut requests = 0;
+        let messages = state
+            .poll(Duration::ZERO, |min, max| {
+                requests += 1;
+                fetch(min, max)
+            })
+            .await
+            .unwrap();
+        assert_eq!((requests, messages.len()), (1, super::PAGE_SIZE));
+
+        // then pages go back until the last seen message, the first message at 1010 has already been yielded
+        state.last_timestamp = Some(1010);
+        state.seen = HashSet::from(["20.d".into()]);
+        let messages = state.poll(Duration::ZERO, fetch).await.unwrap();
+        assert_eq!(messages.len(), 109);
+        assert_eq!(messages.iter().map(|(id, _, _)| id).collect::<HashSet<_>>().len(), 109);
+        assert!(messages.iter().all(|(id, t, _)| *t >= 1010 && id != "20.d"));
+    }
+}
@@ -11,11 +11,16 @@ pub struct IntelResponse {
 
 #[cfg(test)]
 mod tests {
+    use crate::entities::{Mitigation, ModKind, Ornament, PortalHistory, Rarity, Uniques};
+
     #[test]
     fn owned_portal() {
         let s = r#"{"result":["p","R",45599806,12377142,1,85,1,"https://lh3.googleusercontent.com/ht0FYXJzAnMG_yhfC7gxefVtrJ3zW4LGifs7Ek_4_JORVzQ4DovLSQ3RpRnunQYOTOmE_LOrWVmRSRm256BR0ivO_Ns","S. Cipriano - Cimitero",["sc5_p"],false,false,null,1720246737675,[null,null,null,null],[["TerminateThis",5,2550]],"TerminateThis",["","",[]],3]}"#;
         let jd = &mut serde_json::Deserializer::from_str(s);
-        serde_path_to_error::deserialize::<_, super::IntelResponse>(jd).unwrap();
+        let portal = serde_path_to_error::deserialize::<_, super::IntelResponse>(jd).unwrap().result;
+        assert_eq!(portal.computed_health(), Some(portal.health));
+        assert_eq!(portal.link_range(), Some(0_f64));
+        assert_eq!(portal.days_to_decay(), Some(6));
     }
 
     #[test]
@@ -25,10 +30,68 @@ mod tests {
         serde_path_to_error::deserialize::<_, super::IntelResponse>(jd).unwrap();
     }
 
+    #[test]
+    fn artifacts() {
+        let s = r#"{"result":["p","E",45599078,12341800,1,0,0,null,"Shard Target",["ap1","peBB_BATTLE_RARE","sc5_p","xyz"],false,false,[[],[["shard"]]],1711348025166,[null,null,null,null],[],"",["shard","Shard",[3,7]]]}"#;
+        let jd = &mut serde_json::Deserializer::from_str(s);
+        let portal = serde_path_to_error::deserialize::<_, super::IntelResponse>(jd).unwrap().result;
+        assert_eq!(
+            portal.ornaments,
+            [
+                Ornament::Anomaly("ap1".into()),
+                Ornament::BattleBeacon("peBB_BATTLE_RARE".into()),
+                Ornament::ScoutController("sc5_p".into()),
+                Ornament::Unknown("xyz".into())
+            ]
+        );
+        assert!(portal.is_anomaly());
+        assert!(portal.has_battle_beacon());
+        assert!(portal.is_shard_target());
+        assert!(!portal.has_shards());
+        let detail = portal.artifact_detail.unwrap();
+        assert_eq!(detail.kind, "shard");
+        assert_eq!(detail.fragments, [3, 7]);
+    }
+
+    #[test]
+    fn history() {
+        let s = r#"{"result":["p","N",45599078,12341800,1,0,0,null,"Visited",[],false,false,null,1711348025166,[null,null,null,null],[],"",["","",[]],5]}"#;
+        let jd = &mut serde_json::Deserializer::from_str(s);
+        let portal = serde_path_to_error::deserialize::<_, super::IntelResponse>(jd).unwrap().result;
+        assert_eq!(portal.history, Some(PortalHistory { visited: true, captured: false, scout_controlled: true }));
+        // empty artifact placeholder
+        assert!(portal.artifact_detail.is_none());
+
+        let uniques = [&portal].into_iter().collect::<Uniques>();
+        assert_eq!(uniques, Uniques { portals: 1, visited: 1, captured: 0, scout_controlled: 1 });
+
+        // unknown bits don't break deserialization
+        let history = serde_json::from_str::<PortalHistory>("1026").unwrap();
+        assert_eq!(history, PortalHistory { visited: false, captured: true, scout_controlled: false });
+    }
+
     #[test]
     fn machina_portal() {
         let s = r#"{"result":["p","M",45590126,12338500,5,1,8,"https://lh3.googleusercontent.com/jxuZSfHc52kZnwWbz9a9FxFMKjVTenxSWIBeRqH8DVSHtrNig8gam7a9uxOk-tYFMMSJE2RQTZZEFfpFdsAmYt-oThu0","Fontana Ottagonale",["sc5_p"],false,false,null,1720200758276,[["__MACHINA__","SoftBank Ultra Link","VERY_RARE",{"LINK_DEFENSE_BOOST":"1500","OUTGOING_LINKS_BONUS":"8","LINK_RANGE_MULTIPLIER":"5000","REMOVAL_STICKINESS":"150000"}],["__MACHINA__","SoftBank Ultra Link","VERY_RARE",{"LINK_DEFENSE_BOOST":"1500","OUTGOING_LINKS_BONUS":"8","LINK_RANGE_MULTIPLIER":"5000","REMOVAL_STICKINESS":"150000"}],["__MACHINA__","SoftBank Ultra Link","VERY_RARE",{"LINK_DEFENSE_BOOST":"1500","OUTGOING_LINKS_BONUS":"8","LINK_RANGE_MULTIPLIER":"5000","REMOVAL_STICKINESS":"150000"}],["__MACHINA__","SoftBank Ultra Link","VERY_RARE",{"LINK_DEFENSE_BOOST":"1500","OUTGOING_LINKS_BONUS":"8","LINK_RANGE_MULTIPLIER":"5000","REMOVAL_STICKINESS":"150000"}]],[["__MACHINA__",5,30],["__MACHINA__",5,30],["__MACHINA__",5,30],["__MACHINA__",5,30],["__MACHINA__",5,30],["__MACHINA__",5,30],["__MACHINA__",5,30],["__MACHINA__",5,30]],"_\u0336\u0331\u030d_\u0334\u0333\u0349\u0306\u0308\u0301M\u0337\u0354\u0324\u0352\u0104\u0337\u030dC\u0334\u033c\u0315\u0345H\u0336\u0339\u0355\u033c\u033e\u1e2c\u0335\u0307\u033e\u0313N\u0335\u033a\u0355\u0352\u0300\u030d\u00c4\u0334\u031e\u0330\u0301_\u0334\u0326\u0300\u0346\u0313_\u0337\u0323\u0308\u0301",["","",[]],3]}"#;
         let jd = &mut serde_json::Deserializer::from_str(s);
-        serde_path_to_error::deserialize::<_, super::IntelResponse>(jd).unwrap();
+        let res = serde_path_to_error::deserialize::<_, super::IntelResponse>(jd).unwrap();
+        let mods = res.result.mods.as_ref().unwrap();
+        let ultra_link = mods[0].as_ref().unwrap();
+        assert_eq!(ultra_link.name, ModKind::SoftBankUltraLink);
+        assert!(ultra_link.name.is_link_amp());
+        assert_eq!(ultra_link.rarity, Rarity::VeryRare);
+        assert_eq!(ultra_link.stats.link_range_multiplier, Some(5000));
+        assert_eq!(ultra_link.stats.outgoing_links_bonus, Some(8));
+        assert!(ultra_link.stats.other.is_empty());
+
+        let portal = res.result;
+        assert_eq!(portal.total_energy(), Some(240));
+        assert_eq!(portal.max_energy(), Some(24000));
+        assert_eq!(portal.computed_health(), Some(portal.health));
+        assert_eq!(portal.link_amp_boost(), 7.5);
+        assert_eq!(portal.link_range(), Some(750000_f64));
+        assert_eq!(portal.mitigation(0).total, 0);
+        assert_eq!(portal.mitigation(3), Mitigation { shields: 0, links: 187, total: 95, excess: 92 });
+        assert_eq!(portal.days_to_decay(), Some(1));
     }
 }
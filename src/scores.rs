@@ -0,0 +1,138 @@
+use serde::{Deserialize, Deserializer};
+use smol_str::SmolStr;
+
+use crate::{
+    coords::LatLng,
+    entities::Faction,
+    utils::{deserialize_number, deserialize_number_opt},
+};
+
+/// getGameScore response root
+#[derive(Clone, Debug, Deserialize)]
+pub struct GameScoreResponse {
+    /// "result" node
+    pub result: Score,
+}
+
+/// getRegionScoreDetails response root
+#[derive(Clone, Debug, Deserialize)]
+pub struct RegionScoreResponse {
+    /// "result" node
+    pub result: RegionScore,
+}
+
+/// MU per faction
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(from = "RawScore")]
+pub struct Score {
+    /// Enlightened MU
+    pub enlightened: u64,
+    /// Resistance MU
+    pub resistance: u64,
+}
+
+impl Score {
+    /// leading faction, `None` on ties
+    pub fn leader(&self) -> Option<Faction> {
+        match self.enlightened.cmp(&self.resistance) {
+            std::cmp::Ordering::Greater => Some(Faction::Enlightened),
+            std::cmp::Ordering::Less => Some(Faction::Resistance),
+            std::cmp::Ordering::Equal => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct RawScore(
+    #[serde(deserialize_with = "deserialize_number")] u64,
+    #[serde(deserialize_with = "deserialize_number")] u64,
+);
+
+impl From<RawScore> for Score {
+    fn from(RawScore(enlightened, resistance): RawScore) -> Self {
+        Score { enlightened, resistance }
+    }
+}
+
+/// cell score details
+#[derive(Clone, Debug, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RegionScore {
+    /// cell name
+    pub region_name: SmolStr,
+    /// cell corners
+    #[serde(default, deserialize_with = "deserialize_vertices")]
+    pub region_vertices: Vec<LatLng>,
+    /// current cycle average score
+    pub game_score: Score,
+    /// scored checkpoints of the current cycle
+    #[serde(default)]
+    pub score_history: Vec<Checkpoint>,
+    /// top agents of the current cycle
+    #[serde(default)]
+    pub top_agents: Vec<TopAgent>,
+    /// milliseconds to the end of the current cycle
+    #[serde(default, deserialize_with = "deserialize_number_opt")]
+    pub time_to_end_of_base_cycle_ms: Option<u64>,
+}
+
+/// single checkpoint score
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize)]
+#[serde(from = "RawCheckpoint")]
+pub struct Checkpoint {
+    /// checkpoint number inside the cycle
+    pub checkpoint: u32,
+    /// MU measured at the checkpoint
+    pub score: Score,
+}
+
+#[derive(Deserialize)]
+struct RawCheckpoint(
+    #[serde(deserialize_with = "deserialize_number")] u32,
+    #[serde(deserialize_with = "deserialize_number")] u64,
+    #[serde(deserialize_with = "deserialize_number")] u64,
+);
+
+impl From<RawCheckpoint> for Checkpoint {
+    fn from(RawCheckpoint(checkpoint, enlightened, resistance): RawCheckpoint) -> Self {
+        Checkpoint { checkpoint, score: Score { enlightened, resistance } }
+    }
+}
+
+/// cell top agent
+#[derive(Clone, Debug, PartialEq, Eq, Deserialize)]
+pub struct TopAgent {
+    /// nickname
+    pub nick: SmolStr,
+    /// faction
+    pub team: Faction,
+}
+
+fn deserialize_vertices<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<LatLng>, D::Error> {
+    Ok(Vec::<[i64; 2]>::deserialize(deserializer)?.into_iter().map(|[lat, lng]| LatLng::from_e6(lat, lng)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::Faction;
+
+    #[test]
+    fn game_score() {
+        let res: super::GameScoreResponse = serde_json::from_str(r#"{"result":["1093841541","1181212311"]}"#).unwrap();
+        assert_eq!(res.result.enlightened, 1093841541);
+        assert_eq!(res.result.leader(), Some(Faction::Resistance));
+    }
+
+    #[test]
+    fn region_score() {
+        let res: super::RegionScoreResponse = serde_json::from_str(r#"{"result":{"regionName":"EU47-KILO-05","regionVertices":[[45503926,11889648],[45315413,11856222],[45332844,12106531],[45521526,12140466]],"gameScore":["141232","216593"],"scoreHistory":[["3","140000","210000"],["2","142000","220000"],["1","141696","219779"]],"topAgents":[{"nick":"TerminateThis","team":"RESISTANCE"},{"nick":"Other","team":"ENLIGHTENED"}],"timeToEndOfBaseCycleMs":"340800000"}}"#).unwrap();
+        let region = res.result;
+        assert_eq!(region.region_name, "EU47-KILO-05");
+        assert_eq!(region.region_vertices[0].lat_e6(), 45503926);
+        assert_eq!(region.game_score.resistance, 216593);
+        assert_eq!(region.score_history[0].checkpoint, 3);
+        assert_eq!(region.score_history[2].score.enlightened, 141696);
+        assert_eq!(region.top_agents[1].team, Faction::Enlightened);
+        assert_eq!(region.time_to_end_of_base_cycle_ms, Some(340800000));
+    }
+}
@@ -0,0 +1,142 @@
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use smol_str::{SmolStr, ToSmolStr};
+
+use crate::{API_VERSION, entities::Faction, html, player::PLAYER, plexts::parse_faction};
+
+static BANNED: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)account (?:has been )?(?:disabled|banned|suspended|terminated)").unwrap());
+static NOT_ACTIVATED: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(?:not (?:yet )?(?:been )?activated|activate your account)").unwrap());
+
+// only the fields reported by the status, anything else may be missing or null
+#[derive(Deserialize)]
+struct PlayerBrief {
+    #[serde(default)]
+    nickname: Option<SmolStr>,
+    #[serde(default)]
+    team: Option<SmolStr>,
+}
+
+impl PlayerBrief {
+    fn from_page(page: &str) -> Option<Self> {
+        let player = PLAYER.captures(page)?.get(1)?.as_str();
+        serde_json::from_str(player).ok()
+    }
+}
+
+/// Intel session status
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SessionStatus {
+    /// session is valid
+    Valid {
+        /// agent nickname, if exposed by the page
+        nickname: Option<SmolStr>,
+        /// agent faction, if exposed by the page
+        faction: Option<Faction>,
+    },
+    /// Intel session cookies were present, but they aren't valid anymore
+    Expired,
+    /// there is no Intel session, a Facebook login is needed
+    NeedsFacebookLogin,
+    /// account has been banned or disabled
+    Banned,
+    /// account has not been activated
+    NotActivated,
+    /// Intel API version changed since last login
+    VersionOutdated {
+        /// API version currently served by Intel
+        current: SmolStr,
+    },
+}
+
+impl SessionStatus {
+    /// checks if valid
+    pub fn is_valid(&self) -> bool {
+        matches!(self, SessionStatus::Valid { .. })
+    }
+
+    /// classifies the Intel page
+    pub(crate) fn from_page(page: &str, has_session: bool, known_version: Option<&str>) -> Self {
+        // scripts may well contain these sentences, only look at what the agent would read
+        let text = html::body_text(page);
+        if BANNED.is_match(&text) {
+            return SessionStatus::Banned;
+        }
+        if NOT_ACTIVATED.is_match(&text) {
+            return SessionStatus::NotActivated;
+        }
+
+        let Some(current) = API_VERSION.captures(page).and_then(|c| c.get(1)).map(|m| m.as_str()) else {
+            return if has_session { SessionStatus::Expired } else { SessionStatus::NeedsFacebookLogin };
+        };
+        if known_version.is_some_and(|known| known != current) {
+            return SessionStatus::VersionOutdated { current: current.to_smolstr() };
+        }
+
+        let player = PlayerBrief::from_page(page);
+        SessionStatus::Valid {
+            nickname: player.as_ref().and_then(|p| p.nickname.clone()),
+            faction: player.as_ref().and_then(|p| p.team.as_deref()).and_then(parse_faction),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SessionStatus;
+
+    const DASHBOARD: &str = r#"<html><head><script type="text/javascript">var PLAYER = {"ap": "47539381", "available_invites": 0, "energy": 22716, "level": 16, "nickname": "TerminateThis", "team": "RESISTANCE", "xm_capacity": "22800"};</script><script type="text/javascript" src="/jsc/gen_dashboard_8b2f0ea3ad1d6e5e4c1b6a1e9b1d0bd1b0a4dbe0.js"></script></head><body></body></html>"#;
+    const LOGIN: &str = r#"<html><body><a href="https://www.facebook.com/dialog/oauth?client_id=449856365443419">Sign in with Facebook</a></body></html>"#;
+
+    #[test]
+    fn valid() {
+        assert_eq!(
+            SessionStatus::from_page(DASHBOARD, true, None),
+            SessionStatus::Valid {
+                nickname: Some("TerminateThis".into()),
+                faction: Some(crate::entities::Faction::Resistance)
+            }
+        );
+    }
+
+    #[test]
+    fn partial_player() {
+        let page = r#"<html><head><script type="text/javascript">var PLAYER = {"ap": null, "energy": null, "nickname": "TerminateThis", "team": "RESISTANCE"};</script><script type="text/javascript" src="/jsc/gen_dashboard_8b2f0ea3ad1d6e5e4c1b6a1e9b1d0bd1b0a4dbe0.js"></script></head><body></body></html>"#;
+        assert_eq!(
+            SessionStatus::from_page(page, true, None),
+            SessionStatus::Valid {
+                nickname: Some("TerminateThis".into()),
+                faction: Some(crate::entities::Faction::Resistance)
+            }
+        );
+    }
+
+    #[test]
+    fn outdated() {
+        assert_eq!(
+            SessionStatus::from_page(DASHBOARD, true, Some("0000")),
+            SessionStatus::VersionOutdated { current: "8b2f0ea3ad1d6e5e4c1b6a1e9b1d0bd1b0a4dbe0".into() }
+        );
+    }
+
+    #[test]
+    fn script_messages() {
+        let page = DASHBOARD.replace(
+            "<body></body>",
+            r#"<body><script>var errors = {"banned": "Your account has been banned", "inactive": "Please activate your account"};</script></body>"#,
+        );
+        assert!(SessionStatus::from_page(&page, true, None).is_valid());
+    }
+
+    #[test]
+    fn login() {
+        assert_eq!(SessionStatus::from_page(LOGIN, true, None), SessionStatus::Expired);
+        assert_eq!(SessionStatus::from_page(LOGIN, false, None), SessionStatus::NeedsFacebookLogin);
+        assert_eq!(
+            SessionStatus::from_page("<p>Your account has been disabled</p>", true, None),
+            SessionStatus::Banned
+        );
+    }
+}
@@ -1,4 +1,7 @@
+use std::str::FromStr;
+
 use serde::{Deserialize, Deserializer};
+use smol_str::SmolStr;
 
 pub(crate) fn deserialize_coord<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
     let temp = i64::deserialize(deserializer)?;
@@ -9,3 +12,36 @@ pub(crate) fn deserialize_coord_opt<'de, D: Deserializer<'de>>(deserializer: D)
     let temp = Option::<i64>::deserialize(deserializer)?;
     Ok(temp.map(|i| i as f64 / 1000000_f64))
 }
+
+/// Intel serializes some numbers as strings
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Number {
+    Number(u64),
+    String(SmolStr),
+}
+
+pub(crate) fn deserialize_number<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + TryFrom<u64>,
+{
+    match Number::deserialize(deserializer)? {
+        Number::Number(n) => T::try_from(n).map_err(|_| serde::de::Error::custom(format!("{n} out of range"))),
+        Number::String(s) => s.parse().map_err(|_| serde::de::Error::custom(format!("invalid number {s}"))),
+    }
+}
+
+pub(crate) fn deserialize_number_opt<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr + TryFrom<u64>,
+{
+    deserialize_number(deserializer).map(Some)
+}
+
+/// tile entities from a gameEntities array, for tests
+#[cfg(test)]
+pub(crate) fn entities(s: &str) -> crate::entities::IntelEntities {
+    serde_json::from_str(&format!(r#"{{"gameEntities":{s}}}"#)).unwrap()
+}
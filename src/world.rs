@@ -0,0 +1,402 @@
+use std::{
+    collections::{HashMap, HashSet},
+    ops::RangeInclusive,
+};
+
+use smol_str::SmolStr;
+
+use crate::{
+    coords::{BoundingBox, LatLng},
+    entities::{self, Entity, Faction, IntelEntity, IntelField, IntelLink, IntelPortal},
+    plexts, portal_details,
+};
+
+/// portal as stored by the world model
+#[derive(Clone, Debug)]
+pub struct WorldPortal {
+    /// newest known timestamp
+    pub timestamp: i64,
+    /// portal data
+    pub portal: IntelPortal,
+    /// portal data comes from getPortalDetails
+    pub detailed: bool,
+}
+
+/// stored COMM message
+#[derive(Clone, Debug)]
+pub struct WorldPlext {
+    /// message timestamp
+    pub timestamp: i64,
+    /// message
+    pub plext: plexts::Plext,
+}
+
+/// portal query, every criteria must match
+#[derive(Clone, Debug, Default)]
+pub struct Query {
+    bbox: Option<BoundingBox>,
+    faction: Option<Faction>,
+    level: Option<RangeInclusive<u8>>,
+    owner: Option<SmolStr>,
+}
+
+impl Query {
+    /// limits results to a bounding box
+    pub fn bbox(mut self, bbox: BoundingBox) -> Self {
+        self.bbox = Some(bbox);
+        self
+    }
+
+    /// limits results to a faction
+    pub fn faction(mut self, faction: Faction) -> Self {
+        self.faction = Some(faction);
+        self
+    }
+
+    /// limits results to a level range
+    pub fn level(mut self, level: RangeInclusive<u8>) -> Self {
+        self.level = Some(level);
+        self
+    }
+
+    /// limits results to portals owned by an agent, needs portal details
+    pub fn owner(mut self, owner: impl Into<SmolStr>) -> Self {
+        self.owner = Some(owner.into());
+        self
+    }
+
+    fn contains(&self, point: LatLng) -> bool {
+        self.bbox.is_none_or(|bbox| bbox.contains(point))
+    }
+
+    fn matches(&self, portal: &IntelPortal) -> bool {
+        self.contains(portal.coords())
+            && self.faction.is_none_or(|f| f == portal.faction)
+            && self.level.as_ref().is_none_or(|l| l.contains(&portal.level))
+            && self.owner.as_ref().is_none_or(|o| portal.owner.as_ref() == Some(o))
+    }
+}
+
+/// in-memory world model, merging every endpoint results
+#[derive(Clone, Debug, Default)]
+pub struct World {
+    portals: HashMap<SmolStr, WorldPortal>,
+    links: HashMap<SmolStr, Entity<IntelLink>>,
+    fields: HashMap<SmolStr, Entity<IntelField>>,
+    plexts: HashMap<SmolStr, WorldPlext>,
+    portal_links: HashMap<SmolStr, HashSet<SmolStr>>,
+    portal_fields: HashMap<SmolStr, HashSet<SmolStr>>,
+}
+
+impl World {
+    /// ingests a single entity, older data never replaces newer one
+    pub fn ingest_entity(&mut self, entity: IntelEntity) {
+        match entity {
+            IntelEntity::Portal(p) => self.ingest_portal(p.id, p.timestamp, p.entity, false),
+            IntelEntity::Link(l) => {
+                if self.links.get(&l.id).is_some_and(|old| old.timestamp > l.timestamp) {
+                    return;
+                }
+                for portal_id in [&l.entity.origin_portal_id, &l.entity.destination_portal_id] {
+                    self.portal_links.entry(portal_id.clone()).or_default().insert(l.id.clone());
+                }
+                self.links.insert(l.id.clone(), l);
+            }
+            IntelEntity::Field(f) => {
+                if self.fields.get(&f.id).is_some_and(|old| old.timestamp > f.timestamp) {
+                    return;
+                }
+                for portal in &f.entity.portals {
+                    self.portal_fields.entry(portal.id.clone()).or_default().insert(f.id.clone());
+                }
+                self.fields.insert(f.id.clone(), f);
+            }
+            IntelEntity::Other(_) => {}
+        }
+    }
+
+    /// ingests getEntities results, as returned by `get_entities_in_range`
+    pub fn ingest_entities(&mut self, entities: entities::IntelEntities) {
+        for entity in entities.entities {
+            self.ingest_entity(entity);
+        }
+    }
+
+    /// replaces a bounding box contents with a fresh scan covering all of it, as returned by `get_entities_in_range`
+    /// with the same level and health filters and the default zoom
+    ///
+    /// portals inside the box that the filters would have returned are evicted when missing from the scan,
+    /// links and fields with an anchor inside the box only on unfiltered scans, since filtered ones can't tell them apart
+    pub fn replace_area(
+        &mut self,
+        bbox: BoundingBox,
+        min_level: Option<u8>,
+        max_level: Option<u8>,
+        health: Option<u8>,
+        scan: impl IntoIterator<Item = entities::IntelEntities>,
+    ) {
+        let scan = scan.into_iter().flat_map(|entities| entities.entities).collect::<Vec<_>>();
+        let ids = scan
+            .iter()
+            .filter_map(|entity| match entity {
+                IntelEntity::Portal(p) => Some(&p.id),
+                IntelEntity::Link(l) => Some(&l.id),
+                IntelEntity::Field(f) => Some(&f.id),
+                IntelEntity::Other(_) => None,
+            })
+            .cloned()
+            .collect::<HashSet<_>>();
+
+        let levels = min_level.unwrap_or_default()..=max_level.unwrap_or(8);
+        let health = health.unwrap_or(100);
+        self.portals.retain(|id, p| {
+            ids.contains(id)
+                || !bbox.contains(p.portal.coords())
+                || !levels.contains(&p.portal.level)
+                || p.portal.health > health
+        });
+
+        if levels == (0..=8) && health == 100 {
+            let links = self
+                .links
+                .values()
+                .filter(|l| {
+                    !ids.contains(&l.id) && (bbox.contains(l.entity.origin()) || bbox.contains(l.entity.destination()))
+                })
+                .map(|l| l.id.clone())
+                .collect::<Vec<_>>();
+            for id in links {
+                self.remove_link(&id);
+            }
+            let fields = self
+                .fields
+                .values()
+                .filter(|f| !ids.contains(&f.id) && f.entity.portals.iter().any(|p| bbox.contains(p.coords())))
+                .map(|f| f.id.clone())
+                .collect::<Vec<_>>();
+            for id in fields {
+                self.remove_field(&id);
+            }
+        }
+
+        for entity in scan {
+            self.ingest_entity(entity);
+        }
+    }
+
+    /// ingests a getEntities response, as returned by `get_entities_around`, tile errors are skipped
+    pub fn ingest_entities_response(&mut self, response: entities::IntelResponse) {
+        for (_, result) in response.result.map {
+            if let Ok(entities) = result.into_result() {
+                self.ingest_entities(entities);
+            }
+        }
+    }
+
+    /// ingests a getPortalDetails response
+    pub fn ingest_portal_details(&mut self, portal_id: &str, response: portal_details::IntelResponse) {
+        let timestamp = response.result.timestamp;
+        self.ingest_portal(portal_id.into(), timestamp, response.result, true);
+    }
+
+    /// ingests a getPlexts response
+    pub fn ingest_plexts(&mut self, response: plexts::IntelResponse) {
+        for (id, timestamp, wrapper) in response.result {
+            if self.plexts.get(&id).is_some_and(|old| old.timestamp > timestamp) {
+                continue;
+            }
+            self.plexts.insert(id, WorldPlext { timestamp, plext: wrapper.plext });
+        }
+    }
+
+    fn remove_link(&mut self, id: &SmolStr) {
+        let Some(link) = self.links.remove(id) else {
+            return;
+        };
+        for portal_id in [&link.entity.origin_portal_id, &link.entity.destination_portal_id] {
+            remove_from_index(&mut self.portal_links, portal_id, id);
+        }
+    }
+
+    fn remove_field(&mut self, id: &SmolStr) {
+        let Some(field) = self.fields.remove(id) else {
+            return;
+        };
+        for portal in &field.entity.portals {
+            remove_from_index(&mut self.portal_fields, &portal.id, id);
+        }
+    }
+
+    fn ingest_portal(&mut self, id: SmolStr, timestamp: i64, portal: IntelPortal, detailed: bool) {
+        if let Some(old) = self.portals.get_mut(&id) {
+            if old.timestamp > timestamp {
+                return;
+            }
+            // entities don't carry details, keep them as long as the portal didn't change
+            if !detailed && old.detailed && old.portal.timestamp == portal.timestamp {
+                old.timestamp = timestamp;
+                return;
+            }
+        }
+        self.portals.insert(id, WorldPortal { timestamp, portal, detailed });
+    }
+
+    /// portal by id
+    pub fn portal(&self, id: &str) -> Option<&WorldPortal> {
+        self.portals.get(id)
+    }
+
+    /// link by id
+    pub fn link(&self, id: &str) -> Option<&Entity<IntelLink>> {
+        self.links.get(id)
+    }
+
+    /// field by id
+    pub fn field(&self, id: &str) -> Option<&Entity<IntelField>> {
+        self.fields.get(id)
+    }
+
+    /// every portal
+    pub fn portals(&self) -> impl Iterator<Item = (&SmolStr, &WorldPortal)> {
+        self.portals.iter()
+    }
+
+    /// every link
+    pub fn links(&self) -> impl Iterator<Item = &Entity<IntelLink>> {
+        self.links.values()
+    }
+
+    /// every field
+    pub fn fields(&self) -> impl Iterator<Item = &Entity<IntelField>> {
+        self.fields.values()
+    }
+
+    /// every COMM message, oldest first
+    pub fn plexts(&self) -> Vec<(&SmolStr, &WorldPlext)> {
+        let mut plexts = self.plexts.iter().collect::<Vec<_>>();
+        plexts.sort_by_key(|(_, p)| p.timestamp);
+        plexts
+    }
+
+    /// links attached to a portal
+    pub fn links_of(&self, portal_id: &str) -> impl Iterator<Item = &Entity<IntelLink>> {
+        self.portal_links.get(portal_id).into_iter().flatten().filter_map(|id| self.links.get(id))
+    }
+
+    /// fields anchored to a portal
+    pub fn fields_of(&self, portal_id: &str) -> impl Iterator<Item = &Entity<IntelField>> {
+        self.portal_fields.get(portal_id).into_iter().flatten().filter_map(|id| self.fields.get(id))
+    }
+
+    /// portals matching a query
+    pub fn query<'a>(&'a self, query: &'a Query) -> impl Iterator<Item = (&'a SmolStr, &'a WorldPortal)> {
+        self.portals.iter().filter(|(_, p)| query.matches(&p.portal))
+    }
+
+    /// links matching a query, only bounding box and faction are considered, any endpoint in the box matches
+    pub fn query_links<'a>(&'a self, query: &'a Query) -> impl Iterator<Item = &'a Entity<IntelLink>> {
+        self.links.values().filter(|l| {
+            query.faction.is_none_or(|f| f == l.entity.faction)
+                && (query.contains(l.entity.origin()) || query.contains(l.entity.destination()))
+        })
+    }
+
+    /// fields matching a query, only bounding box and faction are considered, any anchor in the box matches
+    pub fn query_fields<'a>(&'a self, query: &'a Query) -> impl Iterator<Item = &'a Entity<IntelField>> {
+        self.fields.values().filter(|f| {
+            query.faction.is_none_or(|faction| faction == f.entity.faction)
+                && f.entity.portals.iter().any(|p| query.contains(p.coords()))
+        })
+    }
+}
+
+fn remove_from_index(index: &mut HashMap<SmolStr, HashSet<SmolStr>>, portal_id: &SmolStr, id: &SmolStr) {
+    if let Some(ids) = index.get_mut(portal_id) {
+        ids.remove(id);
+        if ids.is_empty() {
+            index.remove(portal_id);
+        }
+    }
+}
+
+impl Extend<entities::IntelEntities> for World {
+    fn extend<I: IntoIterator<Item = entities::IntelEntities>>(&mut self, iter: I) {
+        for entities in iter {
+            self.ingest_entities(entities);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{entities::Faction, utils::entities};
+
+    #[test]
+    fn merge() {
+        let mut world = super::World::default();
+        world.ingest_entities(entities(
+            r#"[
+                ["a.16",1000,["p","E",45000000,12000000,5,85,8,null,"A",[],false,false,null,1000,null,null,null,null,0]],
+                ["b.16",1000,["p","R",45010000,12010000,1,100,1,null,"B",[],false,false,null,1000,null,null,null,null,0]],
+                ["x.9",1000,["e","E","a.16",45000000,12000000,"c.16",45010000,12000000]]
+            ]"#,
+        ));
+        let details = serde_json::from_str(r#"{"result":["p","E",45000000,12000000,5,85,8,null,"A",[],false,false,null,1000,[null,null,null,null],[["TerminateThis",5,2550]],"TerminateThis",["","",[]]]}"#).unwrap();
+        world.ingest_portal_details("a.16", details);
+        // same portal state, details survive
+        world.ingest_entities(entities(
+            r#"[["a.16",1000,["p","E",45000000,12000000,5,85,8,null,"A",[],false,false,null,1000,null,null,null,null,0]]]"#,
+        ));
+        assert!(world.portal("a.16").unwrap().detailed);
+        // older data is ignored
+        world.ingest_entities(entities(
+            r#"[["b.16",900,["p","N",45010000,12010000,1,0,0,null,"B",[],false,false,null,900,null,null,null,null,0]]]"#,
+        ));
+        assert_eq!(world.portal("b.16").unwrap().portal.faction, Faction::Resistance);
+        // newer data replaces details
+        world.ingest_entities(entities(
+            r#"[["a.16",2000,["p","N",45000000,12000000,1,0,0,null,"A",[],false,false,null,2000,null,null,null,null,0]]]"#,
+        ));
+        assert!(!world.portal("a.16").unwrap().detailed);
+
+        assert_eq!(world.links_of("c.16").count(), 1);
+        assert_eq!(world.query(&super::Query::default().faction(Faction::Resistance)).count(), 1);
+        assert_eq!(world.query(&super::Query::default().bbox(((44.0, 11.0), (45.005, 12.005)).into())).count(), 1);
+        assert_eq!(world.query(&super::Query::default().level(1..=1)).count(), 2);
+        assert_eq!(world.query_links(&super::Query::default().faction(Faction::Enlightened)).count(), 1);
+    }
+
+    #[test]
+    fn replace_area() {
+        let mut world = super::World::default();
+        world.ingest_entities(entities(
+            r#"[
+                ["a.16",1000,["p","E",45000000,12000000,5,85,8,null,"A",[],false,false,null,1000,null,null,null,null,0]],
+                ["b.16",1000,["p","E",45010000,12010000,5,85,8,null,"B",[],false,false,null,1000,null,null,null,null,0]],
+                ["c.16",1000,["p","R",46000000,13000000,1,100,1,null,"C",[],false,false,null,1000,null,null,null,null,0]],
+                ["x.9",1000,["e","E","a.16",45000000,12000000,"b.16",45010000,12010000]]
+            ]"#,
+        ));
+        // level 6+ scan can't see level 5 portals, nor tell which links are gone
+        world.replace_area(((44.9, 11.9), (45.1, 12.1)).into(), Some(6), None, None, [entities("[]")]);
+        assert!(world.portal("a.16").is_some());
+        assert!(world.portal("b.16").is_some());
+        assert!(world.link("x.9").is_some());
+
+        // b.16 and its link are missing from a full scan, c.16 lies outside the scanned area
+        world.replace_area(
+            ((44.9, 11.9), (45.1, 12.1)).into(),
+            None,
+            None,
+            None,
+            [entities(
+                r#"[["a.16",2000,["p","E",45000000,12000000,5,85,8,null,"A",[],false,false,null,1000,null,null,null,null,0]]]"#,
+            )],
+        );
+        assert!(world.portal("a.16").is_some());
+        assert!(world.portal("b.16").is_none());
+        assert!(world.portal("c.16").is_some());
+        assert!(world.link("x.9").is_none());
+        assert_eq!(world.links_of("a.16").count(), 0);
+    }
+}